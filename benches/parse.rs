@@ -0,0 +1,24 @@
+use std::hint::black_box;
+
+use cookie_bakery::Cookie;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+const FULL: &str = "session=0123456789abcdef; Expires=Tue, 21 Oct 2025 07:28:00 GMT; \
+    Max-Age=3600; Domain=.example.com; Path=/account; Secure; HttpOnly; SameSite=Lax; \
+    Priority=High; SameParty; Foo=bar; Baz; Qux=quux";
+
+fn parse(c: &mut Criterion) {
+    c.bench_function("parse_full_attributes", |b| {
+        b.iter(|| Cookie::parse(black_box(FULL)).unwrap())
+    });
+
+    for segments in [1_000, 10_000, 100_000] {
+        let input = format!("a=b{}", ";".repeat(segments));
+        c.bench_function(&format!("parse_empty_segments_{segments}"), |b| {
+            b.iter(|| Cookie::parse(black_box(&input)).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
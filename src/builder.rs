@@ -1,31 +1,52 @@
-use std::time::Duration;
+use std::{borrow::Cow, time::Duration};
 
 use crate::{
     cookie::{Cookie, CookieStr},
     expires::Expiration,
+    priority::Priority,
     same_site::SameSite,
+    validation::{ValidationIssue, validation_issues},
 };
 
 pub struct CookieBuilder<'a>(Cookie<'a>);
 
 impl<'a> CookieBuilder<'a> {
     pub fn new(name: &'a str, val: &'a str) -> Self {
-        CookieBuilder(Cookie {
-            cookie_string: None,
-            name: CookieStr::Concrete(name.into()),
-            val: CookieStr::Concrete(val.into()),
-            expires: None,
-            max_age: None,
-            domain: None,
-            path: None,
-            secure: None,
-            http_only: None,
-            same_site: None,
-        })
-    }
-
-    pub fn expires(mut self, expires: Expiration) -> Self {
-        self.0.set_expires(expires);
+        CookieBuilder(Cookie::from_name_value(
+            CookieStr::Concrete(name.into()),
+            CookieStr::Concrete(val.into()),
+        ))
+    }
+
+    /// Like `new`, but takes a name and value that may be owned, e.g. `String`s
+    /// computed at runtime.
+    pub fn new_owned<N, V>(name: N, val: V) -> CookieBuilder<'static>
+    where
+        N: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        CookieBuilder(Cookie::from_name_value(
+            CookieStr::Concrete(name.into()),
+            CookieStr::Concrete(val.into()),
+        ))
+    }
+
+    /// Sets `Expires`, taking either an `Expiration` or a date directly.
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use cookie_bakery::{Cookie, Expiration};
+    ///
+    /// let at = Utc::now() + Duration::days(7);
+    /// let cookie = Cookie::builder("id", "1").expires(at).build();
+    ///
+    /// assert_eq!(cookie.expires(), Some(Expiration::DateTime(at)));
+    /// ```
+    pub fn expires<E>(mut self, expires: E) -> Self
+    where
+        E: Into<Expiration>,
+    {
+        self.0.set_expires(expires.into());
         self
     }
 
@@ -44,6 +65,11 @@ impl<'a> CookieBuilder<'a> {
         self
     }
 
+    pub fn scope(mut self, domain: &'a str, path: &'a str) -> Self {
+        self.0.scope(domain, path);
+        self
+    }
+
     pub fn secure(mut self, secure: bool) -> Self {
         self.0.set_secure(secure);
         self
@@ -59,9 +85,68 @@ impl<'a> CookieBuilder<'a> {
         self
     }
 
+    /// Sets `Partitioned`, which browsers only accept together with `Secure`.
+    ///
+    /// ```
+    /// use cookie_bakery::{Cookie, Priority, SameSite};
+    ///
+    /// let cookie = Cookie::builder("id", "1")
+    ///     .path("/")
+    ///     .secure(true)
+    ///     .same_site(SameSite::None)
+    ///     .partitioned(true)
+    ///     .priority(Priority::High)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     cookie.to_string(),
+    ///     "id=1; Path=/; Secure; SameSite=None; Partitioned; Priority=High"
+    /// );
+    /// ```
+    pub fn partitioned(mut self, partitioned: bool) -> Self {
+        self.0.set_partitioned(partitioned);
+        self
+    }
+
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.0.set_priority(priority);
+        self
+    }
+
+    pub fn extension(mut self, key: &'a str, val: Option<&'a str>) -> Self {
+        self.0.add_extension(key, val);
+        self
+    }
+
+    /// Adds several extension attributes, serialized in the given order.
+    pub fn extensions<I>(mut self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, Option<&'a str>)>,
+    {
+        for (key, val) in attrs {
+            self.0.add_extension(key, val);
+        }
+        self
+    }
+
     pub fn build(self) -> Cookie<'a> {
         self.0
     }
+
+    /// Builds the cookie, failing on the first validation issue found.
+    pub fn build_checked(self) -> Result<Cookie<'a>, ValidationIssue> {
+        match validation_issues(&self.0).first() {
+            Some(issue) => Err(*issue),
+            None => Ok(self.0),
+        }
+    }
+
+    /// Builds the cookie regardless of any validation issues, reporting all
+    /// of them alongside it.
+    pub fn build_validated(self) -> (Cookie<'a>, Vec<ValidationIssue>) {
+        let issues = validation_issues(&self.0);
+        (self.0, issues)
+    }
 }
 
 impl<'a> From<Cookie<'a>> for CookieBuilder<'a> {
@@ -69,3 +154,85 @@ impl<'a> From<Cookie<'a>> for CookieBuilder<'a> {
         CookieBuilder(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_validated() {
+        let (cookie, issues) = CookieBuilder::new("bad name", "a;b")
+            .same_site(SameSite::None)
+            .build_validated();
+
+        assert_eq!(cookie.name_value(), ("bad name", "a;b"));
+        assert_eq!(
+            issues,
+            [
+                ValidationIssue::InvalidNameChar,
+                ValidationIssue::InvalidValueChar,
+                ValidationIssue::SameSiteNoneNotSecure,
+            ]
+        );
+
+        let value = "v".repeat(5000);
+        let (_, issues) = CookieBuilder::new("", &value).build_validated();
+        assert_eq!(
+            issues,
+            [ValidationIssue::EmptyName, ValidationIssue::Oversized]
+        );
+
+        let (_, issues) = CookieBuilder::new("id", "\"quoted\"")
+            .secure(true)
+            .build_validated();
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn build_checked() {
+        assert!(matches!(
+            CookieBuilder::new("id", "a b").build_checked(),
+            Err(ValidationIssue::InvalidValueChar)
+        ));
+        assert!(CookieBuilder::new("id", "ab").build_checked().is_ok());
+    }
+
+    #[test]
+    fn new_owned() {
+        fn session() -> (String, String) {
+            (String::from("session"), format!("{:x}", 0xc0ffee))
+        }
+
+        let (name, val) = session();
+        let cookie: Cookie<'static> = CookieBuilder::new_owned(name, val).path("/").build();
+
+        assert_eq!(cookie.name_value(), ("session", "c0ffee"));
+        assert_eq!(cookie.to_string(), "session=c0ffee; Path=/");
+    }
+
+    #[test]
+    fn extensions() {
+        let cookie = CookieBuilder::new("id", "1")
+            .secure(true)
+            .extensions([("Zeta", Some("1")), ("Alpha", None), ("Mid", Some("x=y"))])
+            .build();
+
+        assert_eq!(cookie.to_string(), "id=1; Secure; Zeta=1; Alpha; Mid=x=y");
+    }
+
+    #[test]
+    fn partitioned_requires_secure() {
+        let (_, issues) = CookieBuilder::new("id", "1")
+            .partitioned(true)
+            .build_validated();
+        assert_eq!(issues, [ValidationIssue::PartitionedNotSecure]);
+
+        assert!(
+            CookieBuilder::new("id", "1")
+                .partitioned(true)
+                .secure(true)
+                .build_checked()
+                .is_ok()
+        );
+    }
+}
@@ -1,10 +1,30 @@
+use std::ffi::OsStr;
+use std::hash::{Hash, Hasher};
+use std::net::IpAddr;
 use std::time::Duration;
 use std::{borrow::Cow, fmt::Display};
 
 use chrono::{DateTime, Days, Utc};
+use percent_encoding::percent_decode_str;
 
-use crate::parse::{ParseError, parse_cookie};
-use crate::{builder::CookieBuilder, expires::Expiration, same_site::SameSite};
+use crate::parse::{
+    ParseError, ParseLimits, ParseOptions, ParseWarning, ParsedHeader, detect_and_parse,
+    parse_cookie, parse_cookie_into, parse_cookie_line, parse_cookie_os,
+    parse_cookie_with_allowed_attrs, parse_cookie_with_limits, parse_cookie_with_options,
+    parse_cookie_with_warnings, parse_request_cookies, parse_request_with_limits,
+    parse_set_cookie_block,
+};
+use crate::{
+    builder::CookieBuilder,
+    display::{EncodedDisplay, ForwardDisplay, RequestDisplay},
+    expires::Expiration,
+    parts::CookieParts,
+    priority::Priority,
+    same_site::SameSite,
+    validation::{ValidationIssue, is_valid_cookie_value},
+};
+
+const BROWSER_SIZE_LIMIT: usize = 4096;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CookieStr<'a> {
@@ -31,17 +51,18 @@ impl<'a> CookieStr<'a> {
         Some(CookieStr::Indexed(start, end))
     }
 
-    fn as_str<'s>(&'s self, source: Option<&'s Cow<str>>) -> &'s str {
+    pub(crate) fn as_str<'s>(&'s self, source: Option<&'s Cow<str>>) -> &'s str {
         match *self {
             CookieStr::Indexed(i, j) => {
                 let str =
                     source.expect("Source str must be `Some` when converting indexed str to str");
                 &str[i..j]
             }
-            CookieStr::Concrete(ref concrete_str) => &*concrete_str,
+            CookieStr::Concrete(ref concrete_str) => concrete_str,
         }
     }
 
+    #[allow(clippy::ptr_arg)]
     fn to_raw_str<'s, 'b: 's>(&'s self, source: &'s Cow<'b, str>) -> Option<&'s str> {
         match *self {
             CookieStr::Indexed(i, j) => match source {
@@ -61,47 +82,175 @@ impl<'a> CookieStr<'a> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Cookie<'a> {
     pub(crate) cookie_string: Option<Cow<'a, str>>,
     pub(crate) name: CookieStr<'a>,
+    pub(crate) name_raw: Option<CookieStr<'a>>,
     pub(crate) val: CookieStr<'a>,
+    pub(crate) val_raw: Option<CookieStr<'a>>,
     pub(crate) expires: Option<Expiration>,
+    pub(crate) expires_raw: Option<CookieStr<'a>>,
     pub(crate) max_age: Option<Duration>,
+    pub(crate) max_age_raw: Option<CookieStr<'a>>,
     pub(crate) domain: Option<CookieStr<'a>>,
     pub(crate) path: Option<CookieStr<'a>>,
     pub(crate) secure: Option<bool>,
     pub(crate) http_only: Option<bool>,
     pub(crate) same_site: Option<SameSite>,
+    pub(crate) same_site_raw: Option<CookieStr<'a>>,
+    pub(crate) partitioned: Option<bool>,
+    pub(crate) priority: Option<Priority>,
+    // Unknown attributes in the order they appeared, e.g. `SameParty`
+    pub(crate) extensions: Vec<(CookieStr<'a>, Option<CookieStr<'a>>)>,
 }
 
 impl<'a> Cookie<'a> {
+    pub(crate) fn from_name_value(name: CookieStr<'a>, val: CookieStr<'a>) -> Cookie<'a> {
+        Cookie {
+            cookie_string: None,
+            name,
+            name_raw: None,
+            val,
+            val_raw: None,
+            expires: None,
+            expires_raw: None,
+            max_age: None,
+            max_age_raw: None,
+            domain: None,
+            path: None,
+            secure: None,
+            http_only: None,
+            same_site: None,
+            same_site_raw: None,
+            partitioned: None,
+            priority: None,
+            extensions: Vec::new(),
+        }
+    }
+
     pub fn parse(str: &'a str) -> Result<Cookie<'a>, ParseError> {
         parse_cookie(str)
     }
 
+    pub fn parse_with_options(
+        str: &'a str,
+        options: ParseOptions,
+    ) -> Result<Cookie<'a>, ParseError> {
+        parse_cookie_with_options(str, options)
+    }
+
+    /// Parses a borrowed or owned input. Passing a `String` gives a cookie
+    /// that owns it, without a separate `into_owned`.
+    pub fn parse_from<T>(input: T) -> Result<Cookie<'a>, ParseError>
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        parse_cookie(input)
+    }
+
+    /// Parses `str` into a cookie that owns its data. This copies the input
+    /// once, where `parse` borrows it and allocates nothing for plain values.
+    pub fn parse_to_owned(str: &str) -> Result<Cookie<'static>, ParseError> {
+        parse_cookie(str.to_owned())
+    }
+
+    /// Parses with `ParseOptions::rfc6265_strict`, rejecting anything that
+    /// doesn't conform to the RFC 6265 grammar.
+    pub fn parse_strict(str: &'a str) -> Result<Cookie<'a>, ParseError> {
+        parse_cookie_with_options(str, ParseOptions::rfc6265_strict())
+    }
+
+    pub fn parse_with_limits(str: &'a str, limits: ParseLimits) -> Result<Cookie<'a>, ParseError> {
+        parse_cookie_with_limits(str, limits)
+    }
+
+    pub fn parse_request(str: &'a str) -> impl Iterator<Item = Result<Cookie<'a>, ParseError>> {
+        parse_request_cookies(str)
+    }
+
+    pub fn parse_request_with_limits(
+        str: &'a str,
+        limits: ParseLimits,
+    ) -> Result<Vec<Cookie<'a>>, ParseError> {
+        parse_request_with_limits(str, limits)
+    }
+
+    pub fn parse_with_warnings(
+        str: &'a str,
+    ) -> Result<(Cookie<'a>, Vec<ParseWarning>), ParseError> {
+        parse_cookie_with_warnings(str)
+    }
+
+    pub fn parse_line(str: &'a str) -> Result<(Cookie<'a>, &'a str), ParseError> {
+        parse_cookie_line(str)
+    }
+
+    pub fn parse_set_cookie_block(str: &'a str) -> Vec<Result<Cookie<'a>, ParseError>> {
+        parse_set_cookie_block(str)
+    }
+
+    pub fn parse_os(str: &OsStr) -> Result<Cookie<'static>, ParseError> {
+        parse_cookie_os(str)
+    }
+
+    pub fn parse_encoded(str: &'a str) -> Result<Cookie<'a>, ParseError> {
+        parse_cookie_with_options(str, ParseOptions::encoded())
+    }
+
+    pub fn parse_with_allowed_attrs(
+        str: &'a str,
+        allowed: &[&str],
+    ) -> Result<Cookie<'a>, ParseError> {
+        parse_cookie_with_allowed_attrs(str, allowed)
+    }
+
+    pub fn parse_into(str: &'a str, scratch: &'a mut String) -> Result<Cookie<'a>, ParseError> {
+        parse_cookie_into(str, scratch)
+    }
+
+    pub fn detect_and_parse(str: &'a str) -> Result<ParsedHeader<'a>, ParseError> {
+        detect_and_parse(str)
+    }
+
     pub fn builder(name: &'a str, val: &'a str) -> CookieBuilder<'a> {
         CookieBuilder::new(name, val)
     }
 
+    /// Starts a builder preset with `Secure`, `HttpOnly`, `SameSite=Lax` and
+    /// `Path=/`. Every preset can be overridden through the builder.
+    pub fn secure_builder(name: &'a str, val: &'a str) -> CookieBuilder<'a> {
+        CookieBuilder::new(name, val)
+            .secure(true)
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .path("/")
+    }
+
     pub fn name(&self) -> &str {
         self.name.as_str(self.cookie_string.as_ref())
     }
 
     pub fn name_raw(&self) -> Option<&str> {
-        self.cookie_string
-            .as_ref()
-            .and_then(|s| self.name.to_raw_str(s))
+        let name = self.name_raw.as_ref().unwrap_or(&self.name);
+        self.cookie_string.as_ref().and_then(|s| name.to_raw_str(s))
     }
 
+    /// Values unescaped or percent-decoded at parse time are stored decoded,
+    /// so this is a plain lookup that never decodes again.
     pub fn value(&self) -> &str {
         self.val.as_str(self.cookie_string.as_ref())
     }
 
+    /// Percent-decodes `value()` on demand, borrowing when there is nothing
+    /// to decode. Escapes that don't decode to UTF-8 become U+FFFD.
+    pub fn decoded_value(&self) -> Cow<'_, str> {
+        percent_decode_str(self.value()).decode_utf8_lossy()
+    }
+
     pub fn value_raw(&self) -> Option<&str> {
-        self.cookie_string
-            .as_ref()
-            .and_then(|s| self.val.to_raw_str(s))
+        let val = self.val_raw.as_ref().unwrap_or(&self.val);
+        self.cookie_string.as_ref().and_then(|s| val.to_raw_str(s))
     }
 
     pub fn name_value(&self) -> (&str, &str) {
@@ -112,10 +261,24 @@ impl<'a> Cookie<'a> {
         self.expires
     }
 
+    pub fn expires_raw(&self) -> Option<&str> {
+        match (self.expires_raw.as_ref(), self.cookie_string.as_ref()) {
+            (Some(expires), Some(source)) => expires.to_raw_str(source),
+            _ => None,
+        }
+    }
+
     pub fn max_age(&self) -> Option<Duration> {
         self.max_age
     }
 
+    pub fn max_age_raw(&self) -> Option<&str> {
+        match (self.max_age_raw.as_ref(), self.cookie_string.as_ref()) {
+            (Some(max_age), Some(source)) => max_age.to_raw_str(source),
+            _ => None,
+        }
+    }
+
     pub fn domain(&self) -> Option<&str> {
         match &self.domain {
             Some(domain) => {
@@ -126,6 +289,14 @@ impl<'a> Cookie<'a> {
         }
     }
 
+    /// Returns the domain as parsed or set, keeping a leading `.` that
+    /// `domain()` strips.
+    pub fn domain_with_dot(&self) -> Option<&str> {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.as_str(self.cookie_string.as_ref()))
+    }
+
     pub fn domain_raw(&self) -> Option<&str> {
         match (self.domain.as_ref(), self.cookie_string.as_ref()) {
             (Some(domain), Some(source)) => match domain.to_raw_str(source) {
@@ -162,11 +333,152 @@ impl<'a> Cookie<'a> {
         self.same_site
     }
 
+    pub fn same_site_raw(&self) -> Option<&str> {
+        match (self.same_site_raw.as_ref(), self.cookie_string.as_ref()) {
+            (Some(same_site), Some(source)) => same_site.to_raw_str(source),
+            _ => None,
+        }
+    }
+
+    pub fn partitioned(&self) -> Option<bool> {
+        self.partitioned
+    }
+
+    pub fn priority(&self) -> Option<Priority> {
+        self.priority
+    }
+
+    /// Attributes the crate doesn't know, as key and optional value, in the
+    /// order they were parsed or added.
+    pub fn extensions(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        let source = self.cookie_string.as_ref();
+        self.extensions
+            .iter()
+            .map(move |(key, val)| (key.as_str(source), val.as_ref().map(|v| v.as_str(source))))
+    }
+
+    /// Returns whether the cookie has expired as of now, see `is_expired_at`.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+
+    /// Returns whether the cookie has expired as of `now`. `Max-Age` takes
+    /// precedence over `Expires` (RFC 6265, 5.3), and since the creation time
+    /// isn't tracked only `Max-Age=0` counts as expired. Session cookies never
+    /// expire by time.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        match (self.max_age, self.expires) {
+            (Some(max_age), _) => max_age.is_zero(),
+            (None, Some(Expiration::DateTime(date))) => date <= now,
+            (None, _) => false,
+        }
+    }
+
+    /// Returns how far apart the `Expires` instant and `now + Max-Age` are
+    /// when both are set. Large gaps usually point at a server bug.
+    pub fn expiry_discrepancy(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let (Some(max_age), Some(Expiration::DateTime(expires))) = (self.max_age, self.expires)
+        else {
+            return None;
+        };
+
+        (expires - max_age_deadline(now, max_age))
+            .abs()
+            .to_std()
+            .ok()
+    }
+
+    /// Resolves when the cookie expires if it was received at `created_at`.
+    /// `Max-Age` takes precedence over `Expires`, and a cookie with neither
+    /// is a session cookie.
+    pub fn effective_expiration(&self, created_at: DateTime<Utc>) -> Expiration {
+        match (self.max_age, self.expires) {
+            (Some(max_age), _) => Expiration::DateTime(max_age_deadline(created_at, max_age)),
+            (None, Some(expires)) => expires,
+            (None, None) => Expiration::Session,
+        }
+    }
+
+    /// Compares like `==` but ignores `Expires` and `Max-Age`, e.g. to spot
+    /// the same cookie issued again with a refreshed expiry.
+    pub fn eq_ignoring_expiry(&self, other: &Cookie) -> bool {
+        self.name() == other.name()
+            && self.value() == other.value()
+            && self.domain() == other.domain()
+            && self.path() == other.path()
+            && self.secure_required() == other.secure_required()
+            && self.http_only().unwrap_or(false) == other.http_only().unwrap_or(false)
+            && self.same_site() == other.same_site()
+            && self.partitioned().unwrap_or(false) == other.partitioned().unwrap_or(false)
+            && self.priority() == other.priority()
+            && self.extensions().eq(other.extensions())
+    }
+
+    pub fn secure_required(&self) -> bool {
+        self.secure == Some(true)
+    }
+
+    /// Domain-matches `host` per RFC 6265. A cookie without a `Domain` is
+    /// host-only, and since the origin that set it isn't known to the cookie
+    /// itself it matches no host. Callers tracking the origin have to compare
+    /// against it themselves.
+    ///
+    /// With the `idna` feature both sides are converted to punycode first, so
+    /// `münchen.de` and `xn--mnchen-3ya.de` match each other.
+    pub fn matches_domain(&self, host: &str) -> bool {
+        let Some(domain) = self.domain() else {
+            return false;
+        };
+
+        #[cfg(feature = "idna")]
+        let (domain, host) = (
+            &*crate::idna::to_ascii(domain),
+            &*crate::idna::to_ascii(host),
+        );
+
+        if host.eq_ignore_ascii_case(domain) {
+            return true;
+        }
+
+        let (host_bytes, domain_bytes) = (host.as_bytes(), domain.as_bytes());
+        host_bytes.len() > domain_bytes.len()
+            && host.parse::<IpAddr>().is_err()
+            && host_bytes[host_bytes.len() - domain_bytes.len() - 1] == b'.'
+            && host_bytes[(host_bytes.len() - domain_bytes.len())..]
+                .eq_ignore_ascii_case(domain_bytes)
+    }
+
+    /// Path-matches `path` per RFC 6265. A cookie without a `Path` is treated
+    /// as `Path=/`.
+    pub fn matches_path(&self, path: &str) -> bool {
+        let cookie_path = self.path().unwrap_or("/");
+
+        if path == cookie_path {
+            return true;
+        }
+
+        path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || path[cookie_path.len()..].starts_with('/'))
+    }
+
+    pub fn matches(&self, host: &str, path: &str, secure: bool) -> bool {
+        self.matches_domain(host) && self.matches_path(path) && (!self.secure_required() || secure)
+    }
+
+    pub fn exceeds_size_limit(&self, limit: usize) -> bool {
+        self.name().len() + self.value().len() > limit
+    }
+
+    pub fn exceeds_browser_limit(&self) -> bool {
+        self.exceeds_size_limit(BROWSER_SIZE_LIMIT)
+    }
+
     pub fn set_name<S>(&mut self, name: S) -> &mut Self
     where
         S: Into<Cow<'a, str>>,
     {
         self.name = CookieStr::Concrete(name.into());
+        self.name_raw = None;
         self
     }
 
@@ -175,14 +487,31 @@ impl<'a> Cookie<'a> {
         S: Into<Cow<'a, str>>,
     {
         self.val = CookieStr::Concrete(val.into());
+        self.val_raw = None;
         self
     }
 
+    /// Like `set_value`, but leaves the cookie untouched and fails if `val`
+    /// contains characters outside the cookie-octet grammar. Such values
+    /// need to be encoded first.
+    pub fn try_set_value<S>(&mut self, val: S) -> Result<&mut Self, ValidationIssue>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        let val = val.into();
+        if !is_valid_cookie_value(&val) {
+            return Err(ValidationIssue::InvalidValueChar);
+        }
+
+        Ok(self.set_value(val))
+    }
+
     pub fn set_expires<T>(&mut self, val: T) -> &mut Self
     where
         T: Into<Option<Expiration>>,
     {
         self.expires = val.into();
+        self.expires_raw = None;
         self
     }
     pub fn set_max_age<T>(&mut self, val: T) -> &mut Self
@@ -190,22 +519,60 @@ impl<'a> Cookie<'a> {
         T: Into<Option<Duration>>,
     {
         self.max_age = val.into();
+        self.max_age_raw = None;
         self
     }
 
+    pub fn set_expires_in(&mut self, from_now: chrono::Duration, now: DateTime<Utc>) -> &mut Self {
+        let expires =
+            now.checked_add_signed(from_now)
+                .unwrap_or(if from_now < chrono::Duration::zero() {
+                    DateTime::<Utc>::MIN_UTC
+                } else {
+                    DateTime::<Utc>::MAX_UTC
+                });
+
+        self.set_expires(Expiration::DateTime(expires))
+    }
+
+    pub fn set_expires_in_now(&mut self, from_now: chrono::Duration) -> &mut Self {
+        self.set_expires_in(from_now, Utc::now())
+    }
+
     pub fn unset_expiures(&mut self) -> &mut Self {
         self.expires = None;
+        self.expires_raw = None;
         self
     }
 
+    /// Sets `Domain`. An IP literal can't be a `Domain`, so it unsets the
+    /// attribute instead and leaves the cookie host-only.
     pub fn set_domain<S>(&mut self, val: S) -> &mut Self
     where
         S: Into<Cow<'a, str>>,
     {
-        self.domain = Some(CookieStr::Concrete(val.into()));
+        let val = val.into();
+        self.domain = match is_ip_literal(&val) {
+            true => None,
+            false => Some(CookieStr::Concrete(val)),
+        };
         self
     }
 
+    /// Like `set_domain`, but fails on an IP literal and leaves the cookie
+    /// untouched, the way strict parsing rejects one.
+    pub fn try_set_domain<S>(&mut self, val: S) -> Result<&mut Self, ValidationIssue>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        let val = val.into();
+        if is_ip_literal(&val) {
+            return Err(ValidationIssue::IpLiteralDomain);
+        }
+
+        Ok(self.set_domain(val))
+    }
+
     pub fn unset_domain(&mut self) -> &mut Self {
         self.domain = None;
         self
@@ -224,6 +591,15 @@ impl<'a> Cookie<'a> {
         self
     }
 
+    pub fn scope<D, P>(&mut self, domain: D, path: P) -> &mut Self
+    where
+        D: Into<Cow<'a, str>>,
+        P: Into<Cow<'a, str>>,
+    {
+        self.set_domain(domain);
+        self.set_path(path)
+    }
+
     pub fn set_secure<T>(&mut self, val: T) -> &mut Self
     where
         T: Into<Option<bool>>,
@@ -245,6 +621,86 @@ impl<'a> Cookie<'a> {
         T: Into<Option<SameSite>>,
     {
         self.same_site = val.into();
+        self.same_site_raw = None;
+        self
+    }
+
+    pub fn with_expires<T>(mut self, val: T) -> Self
+    where
+        T: Into<Option<Expiration>>,
+    {
+        self.set_expires(val);
+        self
+    }
+
+    pub fn with_max_age<T>(mut self, val: T) -> Self
+    where
+        T: Into<Option<Duration>>,
+    {
+        self.set_max_age(val);
+        self
+    }
+
+    pub fn with_domain<S>(mut self, val: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.set_domain(val);
+        self
+    }
+
+    pub fn with_path<S>(mut self, val: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.set_path(val);
+        self
+    }
+
+    pub fn with_http_only<T>(mut self, val: T) -> Self
+    where
+        T: Into<Option<bool>>,
+    {
+        self.set_http_only(val);
+        self
+    }
+
+    pub fn with_same_site<T>(mut self, val: T) -> Self
+    where
+        T: Into<Option<SameSite>>,
+    {
+        self.set_same_site(val);
+        self
+    }
+
+    pub fn set_partitioned<T>(&mut self, val: T) -> &mut Self
+    where
+        T: Into<Option<bool>>,
+    {
+        self.partitioned = val.into();
+        self
+    }
+
+    pub fn set_priority<T>(&mut self, val: T) -> &mut Self
+    where
+        T: Into<Option<Priority>>,
+    {
+        self.priority = val.into();
+        self
+    }
+
+    /// Appends an attribute the crate doesn't know, written as `key` or
+    /// `key=val`. A bare `None` needs its type spelled out, e.g.
+    /// `None::<&str>`.
+    pub fn add_extension<K, V>(&mut self, key: K, val: Option<V>) -> &mut Self
+    where
+        K: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        self.extensions.push((
+            CookieStr::Concrete(key.into()),
+            val.map(|v| CookieStr::Concrete(v.into())),
+        ));
         self
     }
 
@@ -264,6 +720,9 @@ impl<'a> Cookie<'a> {
         self
     }
 
+    /// Turns the cookie into one that deletes it: empty value, `Max-Age=0`
+    /// and an `Expires` in the past. Domain and path are left untouched, since
+    /// the browser only deletes a cookie whose domain and path match.
     pub fn make_removal(&mut self) -> &mut Self {
         self.set_value("");
         self.set_max_age(Duration::from_secs(0));
@@ -276,41 +735,231 @@ impl<'a> Cookie<'a> {
         self
     }
 
+    /// Like `make_removal`, but first sets the domain and path the cookie was
+    /// created with.
+    pub fn make_removal_for<D, P>(&mut self, domain: D, path: P) -> &mut Self
+    where
+        D: Into<Cow<'a, str>>,
+        P: Into<Cow<'a, str>>,
+    {
+        self.set_domain(domain);
+        self.set_path(path);
+        self.make_removal()
+    }
+
+    /// Like `to_string`, but with extension attributes sorted by key, so the
+    /// output doesn't depend on the order they were parsed or added in.
+    pub fn to_string_canonical(&self) -> String {
+        let mut extensions: Vec<_> = self.extensions().collect();
+        extensions.sort();
+
+        let mut out = format!("{}=", self.name());
+        fmt_value(&mut out, self.value())
+            .and_then(|_| self.fmt_known_attributes(&mut out, false))
+            .and_then(|_| fmt_extensions(&mut out, extensions))
+            .expect("writing to a String can't fail");
+        out
+    }
+
+    /// Returns the `Set-Cookie` header name and the `Display` output.
+    pub fn to_header(&self) -> (&'static str, String) {
+        ("Set-Cookie", self.to_string())
+    }
+
+    /// Returns the `Cookie` header name and the bare `name=value` pair, with
+    /// no attributes. Use `as_request` for a percent-encoded pair.
+    pub fn to_request_header(&self) -> (&'static str, String) {
+        ("Cookie", format!("{}={}", self.name(), self.value()))
+    }
+
+    /// Creates a cookie that tells the browser to delete the cookie `name`,
+    /// like `make_removal` does for an existing one. The browser only deletes
+    /// a cookie whose domain and path match, so set the ones it was created
+    /// with, e.g. `Cookie::removal("id").with_path("/app")`.
+    pub fn removal(name: &'a str) -> Cookie<'a> {
+        let mut cookie = Cookie::builder(name, "").build();
+        cookie.make_removal();
+        cookie
+    }
+
+    pub fn as_request(&self) -> RequestDisplay<'_, 'a> {
+        RequestDisplay(self)
+    }
+
+    pub fn as_forwarded(&self) -> ForwardDisplay<'_, 'a> {
+        ForwardDisplay(self)
+    }
+
+    pub fn encoded(&self) -> EncodedDisplay<'_, 'a> {
+        EncodedDisplay(self)
+    }
+
+    pub fn into_parts(self) -> CookieParts {
+        let source = self.cookie_string.as_ref();
+
+        CookieParts {
+            name: self.name.as_str(source).to_owned(),
+            value: self.val.as_str(source).to_owned(),
+            expires: self.expires,
+            max_age: self.max_age,
+            domain: self.domain.as_ref().map(|d| d.as_str(source).to_owned()),
+            path: self.path.as_ref().map(|p| p.as_str(source).to_owned()),
+            secure: self.secure,
+            http_only: self.http_only,
+            same_site: self.same_site,
+            partitioned: self.partitioned,
+            priority: self.priority,
+            extensions: self
+                .extensions()
+                .map(|(key, val)| (key.to_owned(), val.map(str::to_owned)))
+                .collect(),
+        }
+    }
+
+    pub fn to_owned_cookie(&self) -> Cookie<'static> {
+        self.clone().into_owned()
+    }
+
+    pub fn clone_with<F>(&self, f: F) -> Cookie<'static>
+    where
+        F: FnOnce(&mut Cookie<'static>),
+    {
+        let mut cookie = self.to_owned_cookie();
+        f(&mut cookie);
+        cookie
+    }
+
     pub fn into_owned(self) -> Cookie<'static> {
         Cookie {
             cookie_string: self.cookie_string.map(|s| s.into_owned().into()),
             name: self.name.into_owned(),
+            name_raw: self.name_raw.map(|s| s.into_owned()),
             val: self.val.into_owned(),
+            val_raw: self.val_raw.map(|s| s.into_owned()),
             expires: self.expires,
+            expires_raw: self.expires_raw.map(|s| s.into_owned()),
             max_age: self.max_age,
-            domain: self.domain.map(|s| s.into_owned().into()),
-            path: self.path.map(|s| s.into_owned().into()),
+            max_age_raw: self.max_age_raw.map(|s| s.into_owned()),
+            domain: self.domain.map(|s| s.into_owned()),
+            path: self.path.map(|s| s.into_owned()),
             secure: self.secure,
             http_only: self.http_only,
             same_site: self.same_site,
+            same_site_raw: self.same_site_raw.map(|s| s.into_owned()),
+            partitioned: self.partitioned,
+            priority: self.priority,
+            extensions: self
+                .extensions
+                .into_iter()
+                .map(|(key, val)| (key.into_owned(), val.map(|v| v.into_owned())))
+                .collect(),
         }
     }
 }
 
+// Accepts the bracketed IPv6 form as written in URLs, e.g. `[::1]`
+pub(crate) fn is_ip_literal(domain: &str) -> bool {
+    let domain = domain.strip_prefix('.').unwrap_or(domain);
+    let domain = domain
+        .strip_prefix('[')
+        .and_then(|d| d.strip_suffix(']'))
+        .unwrap_or(domain);
+
+    domain.parse::<IpAddr>().is_ok()
+}
+
+// Saturates at DateTime::<Utc>::MAX_UTC instead of overflowing
+fn max_age_deadline(from: DateTime<Utc>, max_age: Duration) -> DateTime<Utc> {
+    chrono::Duration::from_std(max_age)
+        .ok()
+        .and_then(|max_age| from.checked_add_signed(max_age))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+/// Builds a `Set-Cookie` value that deletes the cookie `name`. Browsers only
+/// delete a cookie when `path` and `domain` match the ones it was set with.
+pub fn removal_header(name: &str, path: Option<&str>, domain: Option<&str>) -> String {
+    let mut cookie = Cookie::removal(name);
+    if let Some(path) = path {
+        cookie.set_path(path);
+    }
+    if let Some(domain) = domain {
+        cookie.set_domain(domain);
+    }
+
+    cookie.to_string()
+}
+
+// Compares what the cookie means rather than how it was written, so the
+// leading dot of `Domain` and the raw attribute spellings are ignored, and an
+// unset flag like `Secure` equals one explicitly set to `false`
+impl<'a, 'b> PartialEq<Cookie<'b>> for Cookie<'a> {
+    fn eq(&self, other: &Cookie<'b>) -> bool {
+        self.eq_ignoring_expiry(other)
+            && self.expires() == other.expires()
+            && self.max_age() == other.max_age()
+    }
+}
+
+impl Eq for Cookie<'_> {}
+
+// Only the name, domain and path are hashed, the identity browsers key cookies
+// by. A cookie with an updated value or expiry hashes the same as the old one,
+// `==` still tells them apart
+impl Hash for Cookie<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+        self.domain().hash(state);
+        self.path().hash(state);
+    }
+}
+
 impl<'a> Display for Cookie<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}={}",
-            self.name.as_str(self.cookie_string.as_ref()),
-            self.val.as_str(self.cookie_string.as_ref())
-        )?;
-
-        if self.expires.is_some() {
-            match self.expires.as_ref().unwrap() {
-                Expiration::DateTime(date) => {
-                    write!(f, "; Expires={} GMT", date.format("%a, %d %b %Y %H:%M:%S"))?;
-                }
-                Expiration::Session => {}
+        self.fmt_set_cookie(f, false)
+    }
+}
+
+impl<'a> Cookie<'a> {
+    // With `raw` set, values are written exactly as they were parsed if possible
+    pub(crate) fn fmt_set_cookie(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        raw: bool,
+    ) -> std::fmt::Result {
+        let name = self.name_raw().filter(|_| raw).unwrap_or(self.name());
+        write!(f, "{}=", name)?;
+        match self.value_raw().filter(|_| raw) {
+            Some(value) => f.write_str(value)?,
+            None => fmt_value(f, self.value())?,
+        }
+
+        self.fmt_attributes(f, raw)
+    }
+
+    pub(crate) fn fmt_attributes(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        raw: bool,
+    ) -> std::fmt::Result {
+        self.fmt_known_attributes(f, raw)?;
+        fmt_extensions(f, self.extensions())
+    }
+
+    fn fmt_known_attributes<W: std::fmt::Write>(&self, f: &mut W, raw: bool) -> std::fmt::Result {
+        match (self.expires_raw().filter(|_| raw), self.expires.as_ref()) {
+            (Some(expires), _) => write!(f, "; Expires={}", expires)?,
+            // `format` never consults the system locale, unlike chrono's
+            // `format_localized`, so names are always the English ones
+            (None, Some(Expiration::DateTime(date))) => {
+                write!(f, "; Expires={} GMT", date.format("%a, %d %b %Y %H:%M:%S"))?
             }
+            _ => {}
         }
-        if let Some(max_age) = self.max_age {
-            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        match (self.max_age_raw().filter(|_| raw), self.max_age) {
+            (Some(max_age), _) => write!(f, "; Max-Age={}", max_age)?,
+            (None, Some(max_age)) => write!(f, "; Max-Age={}", max_age.as_secs())?,
+            _ => {}
         }
         if let Some(domain) = self.domain.as_ref() {
             write!(f, "; Domain={}", domain.as_str(self.cookie_string.as_ref()))?;
@@ -324,10 +973,704 @@ impl<'a> Display for Cookie<'a> {
         if let Some(true) = self.http_only {
             write!(f, "; HttpOnly")?;
         }
-        if let Some(same_site) = self.same_site.as_ref() {
-            write!(f, "; SameSite={:?}", same_site)?;
+        match (
+            self.same_site_raw().filter(|_| raw),
+            self.same_site.as_ref(),
+        ) {
+            (Some(same_site), _) => write!(f, "; SameSite={}", same_site)?,
+            (None, Some(same_site)) => write!(f, "; SameSite={:?}", same_site)?,
+            _ => {}
+        }
+        if let Some(true) = self.partitioned {
+            write!(f, "; Partitioned")?;
+        }
+        if let Some(priority) = self.priority {
+            write!(f, "; Priority={:?}", priority)?;
         }
 
         Ok(())
     }
 }
+
+// Quotes a value outside the cookie-octet grammar, escaping `"` and `\`, so
+// that parsing the output gives the value back
+fn fmt_value<W: std::fmt::Write>(f: &mut W, value: &str) -> std::fmt::Result {
+    if is_valid_cookie_value(value) {
+        return f.write_str(value);
+    }
+
+    f.write_char('"')?;
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+fn fmt_extensions<'e, W: std::fmt::Write>(
+    f: &mut W,
+    extensions: impl IntoIterator<Item = (&'e str, Option<&'e str>)>,
+) -> std::fmt::Result {
+    for (key, val) in extensions {
+        match val {
+            Some(val) => write!(f, "; {}={}", key, val)?,
+            None => write!(f, "; {}", key)?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Datelike;
+
+    use super::*;
+
+    #[test]
+    fn scope() {
+        let mut cookie = Cookie::builder("name", "value").build();
+        cookie.scope("example.com", "/account");
+
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.path(), Some("/account"));
+        assert_eq!(
+            cookie.to_string(),
+            "name=value; Domain=example.com; Path=/account"
+        );
+
+        let cookie = Cookie::builder("name", "value")
+            .scope("example.org", "/")
+            .build();
+
+        assert_eq!(cookie.to_string(), "name=value; Domain=example.org; Path=/");
+    }
+
+    #[test]
+    fn secure_builder() {
+        let cookie = Cookie::secure_builder("id", "1").build();
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.http_only(), Some(true));
+        assert!(matches!(cookie.same_site(), Some(SameSite::Lax)));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(
+            cookie.to_string(),
+            "id=1; Path=/; Secure; HttpOnly; SameSite=Lax"
+        );
+
+        let cookie = Cookie::secure_builder("id", "1")
+            .same_site(SameSite::Strict)
+            .http_only(false)
+            .path("/app")
+            .build();
+        assert_eq!(
+            cookie.to_string(),
+            "id=1; Path=/app; Secure; SameSite=Strict"
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let cookie = Cookie::builder("id", "1")
+            .domain(".example.com")
+            .path("/account")
+            .secure(true)
+            .build();
+
+        assert!(cookie.matches("example.com", "/account", true));
+        assert!(cookie.matches("www.Example.com", "/account/settings", true));
+
+        assert!(!cookie.matches("example.org", "/account", true));
+        assert!(!cookie.matches("notexample.com", "/account", true));
+        assert!(!cookie.matches("example.com", "/accounts", true));
+        assert!(!cookie.matches("example.com", "/", true));
+        assert!(!cookie.matches("example.com", "/account", false));
+
+        let cookie = Cookie::builder("id", "1").domain("0.0.1").build();
+        assert!(!cookie.matches_domain("127.0.0.1"));
+
+        let cookie = Cookie::builder("id", "1").path("/a/").build();
+        assert!(cookie.matches_path("/a/b"));
+        assert!(!cookie.matches_path("/a"));
+
+        let cookie = Cookie::builder("id", "1").build();
+        assert!(!cookie.matches("anything.test", "/any/path", false));
+        assert!(!cookie.matches_domain("example.com"));
+    }
+
+    #[test]
+    fn expires_in() {
+        let now = DateTime::parse_from_rfc3339("2025-10-21T07:28:00Z")
+            .unwrap()
+            .to_utc();
+        let mut cookie = Cookie::builder("id", "1").build();
+
+        cookie.set_expires_in(chrono::Duration::days(7), now);
+        assert!(matches!(
+            cookie.expires(),
+            Some(Expiration::DateTime(date))
+                if date == DateTime::parse_from_rfc3339("2025-10-28T07:28:00Z").unwrap()
+        ));
+
+        cookie.set_expires_in(chrono::Duration::MAX, now);
+        assert!(matches!(
+            cookie.expires(),
+            Some(Expiration::DateTime(date)) if date == DateTime::<Utc>::MAX_UTC
+        ));
+    }
+
+    #[test]
+    fn expiry_discrepancy() {
+        let now = DateTime::parse_from_rfc3339("2025-10-21T07:28:00Z")
+            .unwrap()
+            .to_utc();
+
+        let cookie =
+            Cookie::parse("id=1; Max-Age=3600; Expires=Tue, 21 Oct 2025 10:28:00 GMT").unwrap();
+        assert_eq!(
+            cookie.expiry_discrepancy(now),
+            Some(Duration::from_secs(2 * 3600))
+        );
+
+        let cookie =
+            Cookie::parse("id=1; Max-Age=3600; Expires=Tue, 21 Oct 2025 08:28:00 GMT").unwrap();
+        assert_eq!(cookie.expiry_discrepancy(now), Some(Duration::ZERO));
+
+        let cookie = Cookie::parse("id=1; Max-Age=3600").unwrap();
+        assert_eq!(cookie.expiry_discrepancy(now), None);
+    }
+
+    #[test]
+    fn display_date_names_are_english() {
+        let months = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        let days = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+        // 2024-01-01 was a Monday
+        let start = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        for offset in 0..366 {
+            let date = start + chrono::Duration::days(offset);
+            let mut cookie = Cookie::builder("id", "1").build();
+            cookie.set_expires(Expiration::DateTime(date));
+
+            let expected = format!(
+                "id=1; Expires={}, {:02} {} {} 00:00:00 GMT",
+                days[offset as usize % 7],
+                date.day(),
+                months[date.month0() as usize],
+                date.year()
+            );
+            assert_eq!(cookie.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn try_set_value() {
+        let mut cookie = Cookie::builder("id", "1").build();
+
+        assert!(cookie.try_set_value("abc").is_ok());
+        assert_eq!(cookie.value(), "abc");
+
+        assert_eq!(
+            cookie.try_set_value("a;b").err(),
+            Some(ValidationIssue::InvalidValueChar)
+        );
+        assert_eq!(cookie.value(), "abc");
+    }
+
+    #[test]
+    fn into_parts() {
+        let cookie =
+            Cookie::parse("id=abc; Max-Age=60; Domain=.example.com; Path=/; Secure; SameSite=Lax")
+                .unwrap();
+        let parts = cookie.into_parts();
+
+        assert_eq!(parts.name, "id");
+        assert_eq!(parts.value, "abc");
+        assert!(parts.expires.is_none());
+        assert_eq!(parts.max_age, Some(Duration::from_secs(60)));
+        assert_eq!(parts.domain.as_deref(), Some(".example.com"));
+        assert_eq!(parts.path.as_deref(), Some("/"));
+        assert_eq!(parts.secure, Some(true));
+        assert_eq!(parts.http_only, None);
+        assert!(matches!(parts.same_site, Some(SameSite::Lax)));
+    }
+
+    #[test]
+    fn to_owned_cookie() {
+        let input = String::from("id=abc; Path=/");
+        let cookie = Cookie::parse(&input).unwrap();
+
+        let owned = cookie.to_owned_cookie();
+        assert_eq!(cookie.name_value(), ("id", "abc"));
+        assert_eq!(cookie.value_raw(), Some("abc"));
+
+        drop(cookie);
+        drop(input);
+
+        assert_eq!(owned.name_value(), ("id", "abc"));
+        assert_eq!(owned.path(), Some("/"));
+    }
+
+    #[test]
+    fn encoded_value_decoded_once() {
+        let input = "id=a%20b%21";
+        let cookie = Cookie::parse_encoded(input).unwrap();
+
+        assert!(matches!(cookie.val, CookieStr::Concrete(Cow::Owned(_))));
+
+        let first = cookie.value();
+        let second = cookie.value();
+        assert_eq!(first, "a b!");
+        assert!(std::ptr::eq(first, second));
+        assert!(!input.as_bytes().as_ptr_range().contains(&first.as_ptr()));
+    }
+
+    #[test]
+    fn clone_with() {
+        let cookie = Cookie::parse("id=abc; Path=/").unwrap();
+        let derived = cookie.clone_with(|c| {
+            c.set_path("/x");
+            c.set_secure(true);
+        });
+
+        assert_eq!(derived.to_string(), "id=abc; Path=/x; Secure");
+        assert_eq!(cookie.to_string(), "id=abc; Path=/");
+    }
+
+    #[test]
+    fn equality_built_and_parsed() {
+        let built = Cookie::builder("id", "abc")
+            .expires(
+                DateTime::parse_from_rfc3339("2025-10-21T07:28:00Z")
+                    .unwrap()
+                    .to_utc(),
+            )
+            .max_age(Duration::from_secs(60))
+            .domain("example.com")
+            .path("/")
+            .secure(true)
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .build();
+
+        let display = built.to_string();
+        let parsed = Cookie::parse(&display).unwrap();
+        assert_eq!(parsed, built);
+        assert_eq!(built, parsed);
+
+        let mut changed = parsed.clone();
+        changed.set_http_only(None);
+        assert_ne!(changed, built);
+    }
+
+    #[test]
+    fn hash_by_identity() {
+        use std::collections::HashSet;
+        use std::hash::{BuildHasher, RandomState};
+
+        let mut set = HashSet::new();
+        set.insert(Cookie::parse("id=1; Domain=.example.com; Path=/").unwrap());
+        set.insert(Cookie::parse("id=1; Domain=example.com; Path=/").unwrap());
+        assert_eq!(set.len(), 1);
+
+        let state = RandomState::new();
+        let old = Cookie::parse("id=1; Path=/; Max-Age=60").unwrap();
+        let updated = Cookie::parse("id=2; Path=/; Max-Age=3600").unwrap();
+        assert_eq!(state.hash_one(&old), state.hash_one(&updated));
+        assert_ne!(old, updated);
+    }
+
+    #[test]
+    fn equality_normalizes_domain_dot() {
+        let dotted = Cookie::parse("id=1; Domain=.example.com").unwrap();
+        let plain = Cookie::parse("id=1; Domain=example.com").unwrap();
+        assert_eq!(dotted, plain);
+
+        let other = Cookie::parse("id=1; Domain=other.com").unwrap();
+        assert_ne!(dotted, other);
+    }
+
+    #[test]
+    fn eq_ignoring_expiry() {
+        let stored =
+            Cookie::parse("id=1; Path=/; Secure; Expires=Tue, 21 Oct 2025 07:28:00 GMT").unwrap();
+        let fresh = Cookie::parse("id=1; Path=/; Secure; Max-Age=3600").unwrap();
+
+        assert!(stored.eq_ignoring_expiry(&fresh));
+        assert_ne!(stored, fresh);
+
+        let other = Cookie::parse("id=2; Path=/; Secure; Max-Age=3600").unwrap();
+        assert!(!other.eq_ignoring_expiry(&fresh));
+    }
+
+    #[test]
+    fn unset_flags_equal_false() {
+        let parsed = Cookie::parse("id=1; Path=/").unwrap();
+        let built = Cookie::builder("id", "1")
+            .path("/")
+            .secure(false)
+            .http_only(false)
+            .partitioned(false)
+            .build();
+
+        assert_eq!(parsed.secure(), None);
+        assert_eq!(built.secure(), Some(false));
+        assert_eq!(parsed, built);
+
+        let secure = Cookie::parse("id=1; Path=/; Secure").unwrap();
+        assert_ne!(secure, built);
+    }
+
+    #[test]
+    fn make_removal_keeps_domain_and_path() {
+        let mut cookie = Cookie::parse("id=abc; Domain=example.com; Path=/app").unwrap();
+        cookie.make_removal();
+
+        let header = cookie.to_string();
+        assert!(header.starts_with("id=; "));
+        assert!(header.contains("; Max-Age=0"));
+        assert!(header.contains("; Domain=example.com"));
+        assert!(header.contains("; Path=/app"));
+
+        let mut cookie = Cookie::parse("id=abc").unwrap();
+        cookie.make_removal_for("example.com", "/app");
+
+        let header = cookie.to_string();
+        assert!(header.starts_with("id=; "));
+        assert!(header.contains("; Domain=example.com"));
+        assert!(header.contains("; Path=/app"));
+    }
+
+    #[test]
+    fn effective_expiration() {
+        let created_at = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let after_a_minute = DateTime::parse_from_rfc3339("2025-01-01T00:01:00Z")
+            .unwrap()
+            .to_utc();
+
+        let cookie = Cookie::parse("a=b; Max-Age=60").unwrap();
+        assert_eq!(
+            cookie.effective_expiration(created_at),
+            Expiration::DateTime(after_a_minute)
+        );
+
+        let input = "a=b; Expires=Tue, 21 Oct 2025 07:28:00 GMT";
+        let cookie = Cookie::parse(input).unwrap();
+        assert_eq!(
+            cookie.effective_expiration(created_at),
+            cookie.expires().unwrap()
+        );
+
+        let input = format!("{input}; Max-Age=60");
+        let cookie = Cookie::parse(&input).unwrap();
+        assert_eq!(
+            cookie.effective_expiration(created_at),
+            Expiration::DateTime(after_a_minute)
+        );
+
+        let cookie = Cookie::parse("a=b").unwrap();
+        assert_eq!(cookie.effective_expiration(created_at), Expiration::Session);
+
+        let cookie = Cookie::builder("a", "b")
+            .max_age(Duration::from_secs(u64::MAX))
+            .build();
+        assert_eq!(
+            cookie.effective_expiration(created_at),
+            Expiration::DateTime(DateTime::<Utc>::MAX_UTC)
+        );
+    }
+
+    #[test]
+    fn is_expired() {
+        let cookie = Cookie::parse("a=b; Expires=Thu, 01 Jan 1970 00:00:00 GMT").unwrap();
+        assert!(cookie.is_expired());
+
+        let cookie = Cookie::parse("a=b; Max-Age=0").unwrap();
+        assert!(cookie.is_expired());
+
+        // Max-Age wins over a past Expires
+        let cookie =
+            Cookie::parse("a=b; Max-Age=60; Expires=Thu, 01 Jan 1970 00:00:00 GMT").unwrap();
+        assert!(!cookie.is_expired());
+
+        let cookie = Cookie::parse("a=b").unwrap();
+        assert!(!cookie.is_expired());
+
+        let cookie = Cookie::builder("a", "b")
+            .expires(Expiration::Session)
+            .build();
+        assert!(!cookie.is_expired());
+    }
+
+    #[test]
+    fn domain_with_dot() {
+        let cookie = Cookie::parse("a=b; Domain=.example.com").unwrap();
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.domain_raw(), Some("example.com"));
+        assert_eq!(cookie.domain_with_dot(), Some(".example.com"));
+
+        let cookie = Cookie::parse("a=b; Domain=example.com").unwrap();
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.domain_raw(), Some("example.com"));
+        assert_eq!(cookie.domain_with_dot(), Some("example.com"));
+
+        let cookie = Cookie::parse("a=b").unwrap();
+        assert_eq!(cookie.domain_with_dot(), None);
+    }
+
+    #[test]
+    fn domain_with_dot_built() {
+        let cookie = Cookie::builder("a", "b").domain(".example.com").build();
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.domain_raw(), None);
+        assert_eq!(cookie.domain_with_dot(), Some(".example.com"));
+
+        let cookie = Cookie::builder("a", "b").domain("example.com").build();
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.domain_with_dot(), Some("example.com"));
+
+        let mut cookie = Cookie::parse("a=b; Domain=example.com").unwrap();
+        cookie.set_domain(String::from(".example.org"));
+        assert_eq!(cookie.domain(), Some("example.org"));
+        assert_eq!(cookie.domain_raw(), None);
+        assert_eq!(cookie.domain_with_dot(), Some(".example.org"));
+    }
+
+    #[test]
+    fn domain_raw_borrows_input() {
+        let input = "a=b; Domain=.example.com";
+        let cookie = Cookie::parse(input).unwrap();
+
+        let raw = cookie.domain_raw().unwrap();
+        assert_eq!(raw, "example.com");
+        assert!(input.as_bytes().as_ptr_range().contains(&raw.as_ptr()));
+        assert_eq!(
+            raw.as_ptr(),
+            input[input.find("example").unwrap()..].as_ptr()
+        );
+
+        // Raw accessors only hand out slices of borrowed input
+        let cookie = Cookie::parse_to_owned(input).unwrap();
+        assert_eq!(cookie.domain_raw(), None);
+        assert_eq!(cookie.domain(), Some("example.com"));
+    }
+
+    #[test]
+    fn to_string_canonical() {
+        let a = Cookie::parse("id=1; Zeta=z; Path=/; Alpha; Secure; Mid=m").unwrap();
+        let b = Cookie::parse("id=1; Mid=m; Secure; Alpha; Zeta=z; Path=/").unwrap();
+
+        assert_ne!(a.to_string(), b.to_string());
+        assert_eq!(a.to_string_canonical(), b.to_string_canonical());
+        assert_eq!(
+            a.to_string_canonical(),
+            "id=1; Path=/; Secure; Alpha; Mid=m; Zeta=z"
+        );
+    }
+
+    #[test]
+    fn parse_from_string() {
+        let cookie: Cookie<'static> = {
+            let input = String::from("id=abc; Domain=.example.com; Path=/app; Max-Age=60");
+            Cookie::parse_from(input).unwrap()
+        };
+
+        assert_eq!(cookie.name_value(), ("id", "abc"));
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(cookie.path(), Some("/app"));
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(60)));
+
+        let input = "id=abc; Path=/";
+        let cookie = Cookie::parse_from(input).unwrap();
+        assert_eq!(cookie.path_raw(), Some("/"));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for input in [
+            "id=\"a;b\"; Path=/",
+            "id=\"a b\"; Secure",
+            "id=\"say \\\"hi\\\"\"",
+            "id=\"back\\\\slash\"",
+            "id=plain; HttpOnly",
+        ] {
+            let cookie = Cookie::parse(input).unwrap();
+            let output = cookie.to_string();
+            assert_eq!(Cookie::parse(&output).unwrap(), cookie, "{output}");
+        }
+
+        let cookie = Cookie::parse("id=\"a;b\"; Path=/").unwrap();
+        assert_eq!(cookie.value(), "a;b");
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.to_string(), "id=\"a;b\"; Path=/");
+
+        let cookie = Cookie::builder("id", "a b").build();
+        assert_eq!(cookie.to_string(), "id=\"a b\"");
+        assert_eq!(Cookie::parse(&cookie.to_string()).unwrap(), cookie);
+    }
+
+    #[test]
+    fn removal() {
+        let cookie = Cookie::removal("id").with_path("/app");
+
+        assert_eq!(cookie.name_value(), ("id", ""));
+        assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+        assert!(matches!(cookie.expires(), Some(Expiration::DateTime(date)) if date < Utc::now()));
+        assert_eq!(cookie.path(), Some("/app"));
+        assert!(cookie.is_expired_at(Utc::now()));
+    }
+
+    #[test]
+    fn removal_header() {
+        let header = super::removal_header("id", Some("/app"), Some("example.com"));
+        assert!(header.starts_with("id=; "));
+        assert!(header.contains("; Max-Age=0"));
+        assert!(header.contains("; Path=/app"));
+        assert!(header.contains("; Domain=example.com"));
+
+        let header = super::removal_header("id", None, None);
+        assert!(!header.contains("Path"));
+        assert!(!header.contains("Domain"));
+    }
+
+    #[test]
+    fn extensions() {
+        let cookie = Cookie::parse("a=b; SameParty; Secure").unwrap();
+        assert_eq!(
+            cookie.extensions().collect::<Vec<_>>(),
+            [("SameParty", None)]
+        );
+        assert_eq!(cookie.to_string(), "a=b; Secure; SameParty");
+        assert_eq!(Cookie::parse(&cookie.to_string()).unwrap(), cookie);
+
+        let mut cookie = Cookie::builder("a", "b").build();
+        cookie
+            .add_extension("Foo", Some("bar"))
+            .add_extension("Baz", None::<&str>)
+            .add_extension("Qux", Some(String::from("1")));
+        assert_eq!(cookie.to_string(), "a=b; Foo=bar; Baz; Qux=1");
+        assert_eq!(cookie.into_owned().to_string(), "a=b; Foo=bar; Baz; Qux=1");
+    }
+
+    #[test]
+    fn with_setters() {
+        let cookie = Cookie::parse("id=1; Max-Age=60")
+            .unwrap()
+            .with_domain("example.com")
+            .with_path("/")
+            .with_max_age(None)
+            .with_http_only(true)
+            .with_same_site(SameSite::Strict);
+        assert_eq!(
+            cookie.to_string(),
+            "id=1; Domain=example.com; Path=/; HttpOnly; SameSite=Strict"
+        );
+
+        let cookie = cookie
+            .with_expires(Expiration::Session)
+            .with_same_site(None)
+            .with_max_age(Duration::from_secs(10));
+        assert_eq!(cookie.same_site(), None);
+        assert_eq!(cookie.expires(), Some(Expiration::Session));
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn set_domain_ip_literal() {
+        let mut cookie = Cookie::builder("id", "1").build();
+
+        cookie.set_domain("127.0.0.1");
+        assert_eq!(cookie.domain(), None);
+
+        cookie.set_domain("example.com").set_domain("[::1]");
+        assert_eq!(cookie.domain(), None);
+
+        cookie.set_domain("example.com");
+        assert!(matches!(
+            cookie.try_set_domain("[::1]"),
+            Err(ValidationIssue::IpLiteralDomain)
+        ));
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert!(cookie.try_set_domain("example.org").is_ok());
+        assert_eq!(cookie.domain(), Some("example.org"));
+    }
+
+    #[test]
+    fn parse_to_owned() {
+        let cookie = {
+            let input = String::from("id=abc; Path=/");
+            Cookie::parse_to_owned(&input).unwrap()
+        };
+
+        assert_eq!(cookie.name_value(), ("id", "abc"));
+        assert_eq!(cookie.path(), Some("/"));
+        assert!(Cookie::parse_to_owned("no pair").is_err());
+    }
+
+    #[test]
+    fn decoded_value() {
+        let cookie = Cookie::parse("id=a%20b%3B").unwrap();
+        assert_eq!(cookie.value(), "a%20b%3B");
+        assert!(matches!(cookie.decoded_value(), Cow::Owned(v) if v == "a b;"));
+
+        let cookie = Cookie::parse("id=plain").unwrap();
+        assert!(matches!(cookie.decoded_value(), Cow::Borrowed("plain")));
+
+        let cookie = Cookie::parse("id=%FF").unwrap();
+        assert_eq!(cookie.decoded_value(), "\u{FFFD}");
+    }
+
+    #[test]
+    fn to_header() {
+        let cookie = Cookie::parse("id=abc; Max-Age=60; Domain=example.com; Path=/; Secure; HttpOnly; SameSite=Lax; Partitioned; Foo=bar").unwrap();
+
+        let (name, value) = cookie.to_header();
+        assert_eq!(name, "Set-Cookie");
+        assert_eq!(value, cookie.to_string());
+
+        assert_eq!(
+            cookie.to_request_header(),
+            ("Cookie", String::from("id=abc"))
+        );
+    }
+
+    #[test]
+    fn max_age_zero() {
+        let cookie = Cookie::parse("id=; Max-Age=0; Path=/").unwrap();
+        assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+
+        for now in [
+            DateTime::<Utc>::MIN_UTC,
+            Utc::now(),
+            DateTime::<Utc>::MAX_UTC,
+        ] {
+            assert!(cookie.is_expired_at(now));
+        }
+
+        assert_eq!(cookie.to_string(), "id=; Max-Age=0; Path=/");
+        let mut built = Cookie::builder("id", "").path("/").build();
+        built.set_max_age(Duration::ZERO);
+        assert_eq!(built.to_string(), "id=; Max-Age=0; Path=/");
+    }
+
+    #[test]
+    fn size_limit() {
+        let at_limit = "v".repeat(BROWSER_SIZE_LIMIT - 4);
+        let cookie = Cookie::builder("name", &at_limit).build();
+        assert!(!cookie.exceeds_browser_limit());
+        assert!(cookie.exceeds_size_limit(BROWSER_SIZE_LIMIT - 1));
+
+        let over_limit = "v".repeat(BROWSER_SIZE_LIMIT - 3);
+        let cookie = Cookie::builder("name", &over_limit).build();
+        assert!(cookie.exceeds_browser_limit());
+        assert!(!cookie.exceeds_size_limit(BROWSER_SIZE_LIMIT + 1));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::{self, Display};
+
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+
+use crate::Cookie;
+
+// Everything outside of RFC 6265 cookie-octets, plus `%` so the output decodes
+// back to the same value
+const VALUE_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b',')
+    .add(b';')
+    .add(b'\\')
+    .add(b'%');
+
+const NAME_ENCODE_SET: &AsciiSet = &VALUE_ENCODE_SET.add(b'=');
+
+/// Writes a cookie as a percent-encoded `name=value` pair for a `Cookie`
+/// request header.
+pub struct RequestDisplay<'c, 'a>(pub(crate) &'c Cookie<'a>);
+
+impl Display for RequestDisplay<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}={}",
+            utf8_percent_encode(self.0.name(), NAME_ENCODE_SET),
+            utf8_percent_encode(self.0.value(), VALUE_ENCODE_SET)
+        )
+    }
+}
+
+/// Writes a cookie as a `Set-Cookie` value with the name and value
+/// percent-encoded, so reserved characters like `;` survive. Reading it back
+/// with `Cookie::parse_encoded` gives the original name and value.
+pub struct EncodedDisplay<'c, 'a>(pub(crate) &'c Cookie<'a>);
+
+impl Display for EncodedDisplay<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        RequestDisplay(self.0).fmt(f)?;
+        self.0.fmt_attributes(f, false)
+    }
+}
+
+/// Writes a cookie as a `Set-Cookie` value reusing the exact parsed text of
+/// its value and attribute values (e.g. `SameSite=lax`), so forwarding a parsed
+/// cookie doesn't change its bytes. Attribute names and order are always
+/// canonical, and values set after parsing are written like `Display` does.
+pub struct ForwardDisplay<'c, 'a>(pub(crate) &'c Cookie<'a>);
+
+impl Display for ForwardDisplay<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_set_cookie(f, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use super::*;
+    use crate::SameSite;
+
+    #[test]
+    fn forward_display() {
+        let input =
+            "id=\"abc\"; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=060; Path=/; SameSite=lax";
+        let cookie = Cookie::parse(input).unwrap();
+
+        assert_eq!(cookie.same_site_raw(), Some("lax"));
+        assert_eq!(cookie.as_forwarded().to_string(), input);
+        assert_eq!(
+            cookie.to_string(),
+            "id=abc; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=60; Path=/; SameSite=Lax"
+        );
+
+        let mut cookie = cookie;
+        cookie.set_same_site(SameSite::Strict);
+        assert_eq!(cookie.same_site_raw(), None);
+        assert!(
+            cookie
+                .as_forwarded()
+                .to_string()
+                .ends_with("SameSite=Strict")
+        );
+    }
+
+    #[test]
+    fn encoded_display() {
+        let cookie = Cookie::builder("na=me", "a; b,\"c\"%")
+            .path("/")
+            .secure(true)
+            .build();
+
+        let encoded = cookie.encoded().to_string();
+        assert_eq!(encoded, "na%3Dme=a%3B%20b%2C%22c%22%25; Path=/; Secure");
+
+        let parsed = Cookie::parse_encoded(&encoded).unwrap();
+        assert_eq!(parsed.name_value(), ("na=me", "a; b,\"c\"%"));
+        assert_eq!(parsed.path(), Some("/"));
+
+        assert_eq!(
+            cookie.to_string(),
+            "na=me=\"a; b,\\\"c\\\"%\"; Path=/; Secure"
+        );
+    }
+
+    #[test]
+    fn request_display() {
+        let cookie = Cookie::parse("id=a b;c; Path=/; Secure").unwrap();
+
+        let mut buf = String::new();
+        write!(buf, "{}", cookie.as_request()).unwrap();
+        assert_eq!(buf, "id=a%20b");
+
+        let cookie = Cookie::builder("na=me", "100%; \"ok\"").build();
+        assert_eq!(
+            cookie.as_request().to_string(),
+            "na%3Dme=100%25%3B%20%22ok%22"
+        );
+    }
+}
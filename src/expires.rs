@@ -1,7 +1,14 @@
 use chrono::{DateTime, Utc};
 
-#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Expiration {
     Session,
     DateTime(DateTime<Utc>),
 }
+
+impl From<DateTime<Utc>> for Expiration {
+    fn from(date: DateTime<Utc>) -> Self {
+        Expiration::DateTime(date)
+    }
+}
@@ -0,0 +1,113 @@
+use ::http::{HeaderValue, Uri, header::InvalidHeaderValue};
+
+use crate::{Cookie, ParseError, ParseResult, parse::parse_cookie};
+
+impl<'a> Cookie<'a> {
+    /// Checks whether the cookie would be sent with a request to `uri`. An
+    /// `https` or `wss` scheme counts as a secure channel. A `uri` without a
+    /// host, or a host-only cookie, never matches.
+    pub fn matches_uri(&self, uri: &Uri) -> bool {
+        let Some(host) = uri.host() else {
+            return false;
+        };
+        let secure = matches!(uri.scheme_str(), Some("https") | Some("wss"));
+
+        self.matches(host, uri.path(), secure)
+    }
+
+    /// Parses a `Set-Cookie` header value from its raw bytes. Only the name
+    /// and the attribute keys have to be UTF-8; invalid sequences in the
+    /// value or in attribute values are replaced with U+FFFD, so the original
+    /// bytes can't be recovered from the parsed cookie.
+    pub fn parse_header_value(value: &HeaderValue) -> ParseResult<Cookie<'static>> {
+        let bytes = value.as_bytes();
+
+        let mut segments = bytes.split(|&b| b == b';');
+        let name = segments
+            .next()
+            .map(|pair| pair.split(|&b| b == b'=').next().unwrap_or(pair));
+        let keys = segments.map(|attr| attr.split(|&b| b == b'=').next().unwrap_or(attr));
+
+        for key in name.into_iter().chain(keys) {
+            std::str::from_utf8(key).map_err(|_| ParseError::Utf8Error)?;
+        }
+
+        parse_cookie(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+impl<'a> TryFrom<&'a HeaderValue> for Cookie<'a> {
+    type Error = ParseError;
+
+    fn try_from(value: &'a HeaderValue) -> Result<Self, Self::Error> {
+        let value = value.to_str().map_err(|_| ParseError::Utf8Error)?;
+        parse_cookie(value)
+    }
+}
+
+impl TryFrom<&Cookie<'_>> for HeaderValue {
+    type Error = InvalidHeaderValue;
+
+    fn try_from(cookie: &Cookie<'_>) -> Result<Self, Self::Error> {
+        HeaderValue::from_str(&cookie.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_header_value() {
+        let value = HeaderValue::from_bytes(b"id=caf\xe9; Path=/").unwrap();
+        let cookie = Cookie::parse_header_value(&value).unwrap();
+        assert_eq!(cookie.value(), "caf\u{FFFD}");
+        assert_eq!(cookie.path(), Some("/"));
+
+        let value = HeaderValue::from_bytes(b"i\xe9=1").unwrap();
+        assert!(matches!(
+            Cookie::parse_header_value(&value),
+            Err(ParseError::Utf8Error)
+        ));
+
+        let value = HeaderValue::from_bytes(b"id=1; P\xe9th=/").unwrap();
+        assert!(Cookie::parse_header_value(&value).is_err());
+    }
+
+    #[test]
+    fn header_value_conversions() {
+        let value = HeaderValue::from_static("id=abc; Path=/; Secure");
+        let cookie = Cookie::try_from(&value).unwrap();
+        assert_eq!(cookie.name_value(), ("id", "abc"));
+        assert_eq!(cookie.path(), Some("/"));
+
+        let back = HeaderValue::try_from(&cookie).unwrap();
+        assert_eq!(back, value);
+
+        let value = HeaderValue::from_bytes(b"id=caf\xe9").unwrap();
+        assert!(matches!(
+            Cookie::try_from(&value),
+            Err(ParseError::Utf8Error)
+        ));
+
+        let cookie = Cookie::builder("id", "a\nb").build();
+        assert!(HeaderValue::try_from(&cookie).is_err());
+    }
+
+    #[test]
+    fn matches_uri() {
+        let cookie = Cookie::parse("id=1; Domain=example.com; Path=/app; Secure").unwrap();
+
+        let uri = |s: &str| s.parse::<Uri>().unwrap();
+
+        assert!(cookie.matches_uri(&uri("https://example.com/app")));
+        assert!(cookie.matches_uri(&uri("https://www.example.com/app/page?q=1")));
+        assert!(!cookie.matches_uri(&uri("http://example.com/app")));
+        assert!(!cookie.matches_uri(&uri("https://example.com/")));
+        assert!(!cookie.matches_uri(&uri("https://example.com/application")));
+        assert!(!cookie.matches_uri(&uri("/app")));
+
+        let cookie = Cookie::parse("id=1; Path=/").unwrap();
+        assert!(!cookie.matches_uri(&uri("http://example.com")));
+    }
+}
@@ -0,0 +1,32 @@
+use std::borrow::Cow;
+
+// Unicode labels are converted to punycode, ASCII input is returned as is. A
+// name IDNA rejects is kept unchanged and simply won't match its other form
+pub(crate) fn to_ascii(domain: &str) -> Cow<'_, str> {
+    if domain.is_ascii() {
+        return Cow::Borrowed(domain);
+    }
+
+    match ::idna::domain_to_ascii(domain) {
+        Ok(ascii) => Cow::Owned(ascii),
+        Err(_) => Cow::Borrowed(domain),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Cookie;
+
+    #[test]
+    fn matches_idna_domain() {
+        let punycode = Cookie::parse("id=1; Domain=xn--mnchen-3ya.de").unwrap();
+        assert!(punycode.matches_domain("münchen.de"));
+        assert!(punycode.matches_domain("www.münchen.de"));
+        assert!(!punycode.matches_domain("berlin.de"));
+
+        let unicode = Cookie::parse("id=1; Domain=münchen.de").unwrap();
+        assert!(unicode.matches_domain("xn--mnchen-3ya.de"));
+        assert!(unicode.matches_domain("WWW.XN--MNCHEN-3YA.DE"));
+        assert!(unicode.matches_domain("MÜNCHEN.de"));
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    Cookie, ParseError, ParseLimits, expires::Expiration, parse::parse_request_with_limits,
+};
+
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    // Kept in insertion order, oldest first, with the time each was added to
+    // resolve `Max-Age` against
+    cookies: Vec<(Cookie<'static>, DateTime<Utc>)>,
+    limit: Option<usize>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        CookieJar::default()
+    }
+
+    /// Creates a jar holding at most `max` cookies.
+    ///
+    /// Once the limit is exceeded the cookie that expires soonest is evicted,
+    /// with `Max-Age` counted from when the cookie was added and taking
+    /// precedence over `Expires`. Session cookies count as expiring now, so
+    /// they go after already expired cookies but before any that are still
+    /// valid. Ties evict the least recently added cookie.
+    pub fn with_limit(max: usize) -> Self {
+        CookieJar {
+            cookies: Vec::new(),
+            limit: Some(max),
+        }
+    }
+
+    /// Seeds a jar from a client `Cookie:` header. Each `name=value` pair
+    /// becomes a name/value-only cookie; malformed pairs are skipped. A header
+    /// over `ParseLimits::request()` yields an empty jar.
+    pub fn from_request_header(s: &str) -> Self {
+        CookieJar::from_request_header_with_limits(s, ParseLimits::request()).unwrap_or_default()
+    }
+
+    /// Like `from_request_header`, failing with `TooLong` or `TooManyCookies`
+    /// when the header is over `limits`.
+    pub fn from_request_header_with_limits(
+        s: &str,
+        limits: ParseLimits,
+    ) -> Result<Self, ParseError> {
+        let mut jar = CookieJar::new();
+        for cookie in parse_request_with_limits(s, limits)? {
+            jar.add(cookie.into_owned());
+        }
+
+        Ok(jar)
+    }
+
+    /// Adds `cookie`, replacing any cookie with the same name, domain and
+    /// path. Returns the cookie evicted to stay within the limit, if any.
+    pub fn add(&mut self, cookie: Cookie<'static>) -> Option<Cookie<'static>> {
+        self.cookies.retain(|(c, _)| {
+            c.name() != cookie.name() || c.domain() != cookie.domain() || c.path() != cookie.path()
+        });
+        self.cookies.push((cookie, Utc::now()));
+
+        match self.limit {
+            Some(limit) if self.cookies.len() > limit => Some(self.evict()),
+            _ => None,
+        }
+    }
+
+    /// Adds every cookie of `other` in its insertion order. On a name, domain
+    /// and path collision the cookie from `other` wins.
+    pub fn merge(&mut self, other: CookieJar) {
+        for (cookie, _) in other.cookies {
+            self.add(cookie);
+        }
+    }
+
+    /// Returns the most recently added cookie named `name`.
+    pub fn get(&self, name: &str) -> Option<&Cookie<'static>> {
+        self.cookies
+            .iter()
+            .rev()
+            .map(|(cookie, _)| cookie)
+            .find(|c| c.name() == name)
+    }
+
+    /// Removes every cookie named `name`, whatever its domain and path.
+    pub fn remove(&mut self, name: &str) {
+        self.cookies.retain(|(c, _)| c.name() != name);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Cookie<'static>> {
+        self.cookies.iter().map(|(cookie, _)| cookie)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+
+    fn evict(&mut self) -> Cookie<'static> {
+        let now = Utc::now();
+        let soonest = self
+            .cookies
+            .iter()
+            .enumerate()
+            .min_by_key(
+                |(_, (cookie, added_at))| match cookie.effective_expiration(*added_at) {
+                    Expiration::DateTime(date) => date,
+                    Expiration::Session => now,
+                },
+            )
+            .map(|(idx, _)| idx);
+
+        self.cookies.remove(soonest.unwrap_or(0)).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    #[test]
+    fn from_request_header() {
+        let jar = CookieJar::from_request_header("a=1; b=2; c=3");
+        assert_eq!(jar.len(), 3);
+        assert_eq!(jar.get("b").map(|c| c.value()), Some("2"));
+
+        let jar = CookieJar::from_request_header("a=1; junk; =2; b=3");
+        assert_eq!(jar.len(), 2);
+        assert!(jar.get("junk").is_none());
+
+        let header = vec!["a=1"; 181].join("; ");
+        assert!(CookieJar::from_request_header(&header).is_empty());
+        assert!(matches!(
+            CookieJar::from_request_header_with_limits(&header, ParseLimits::request()),
+            Err(ParseError::TooManyCookies)
+        ));
+    }
+
+    #[test]
+    fn add_get_remove() {
+        let mut jar = CookieJar::new();
+        assert!(jar.is_empty());
+
+        jar.add(Cookie::parse("a=1").unwrap().into_owned());
+        jar.add(Cookie::parse("b=2").unwrap().into_owned());
+        jar.add(Cookie::parse("a=3").unwrap().into_owned());
+        jar.add(Cookie::parse("a=4; Path=/app").unwrap().into_owned());
+
+        assert_eq!(jar.len(), 3);
+        assert_eq!(jar.get("a").map(|c| c.value()), Some("4"));
+        assert_eq!(
+            jar.iter().map(|c| c.value()).collect::<Vec<_>>(),
+            ["2", "3", "4"]
+        );
+
+        jar.remove("a");
+        assert!(jar.get("a").is_none());
+        assert_eq!(jar.len(), 1);
+
+        jar.remove("missing");
+        assert_eq!(jar.len(), 1);
+    }
+
+    #[test]
+    fn merge() {
+        let mut defaults = CookieJar::from_request_header("lang=en; theme=dark");
+        let request = CookieJar::from_request_header("theme=light; id=1");
+
+        defaults.merge(request);
+        assert_eq!(defaults.len(), 3);
+        assert_eq!(defaults.get("theme").map(|c| c.value()), Some("light"));
+        assert_eq!(defaults.get("lang").map(|c| c.value()), Some("en"));
+    }
+
+    #[test]
+    fn limit_evicts_soonest_to_expire() {
+        let mut jar = CookieJar::with_limit(2);
+
+        // Far enough out that none of them expire while the test runs
+        let late = Cookie::parse("a=1; Expires=Wed, 01 Jan 2200 12:00:00 GMT").unwrap();
+        let soon = Cookie::parse("b=2; Expires=Fri, 01 Jan 2100 12:00:00 GMT").unwrap();
+        let latest = Cookie::parse("c=3; Expires=Mon, 01 Jan 2300 12:00:00 GMT").unwrap();
+
+        assert!(jar.add(late.into_owned()).is_none());
+        assert!(jar.add(soon.into_owned()).is_none());
+
+        let evicted = jar.add(latest.into_owned()).unwrap();
+        assert_eq!(evicted.name(), "b");
+        assert!(matches!(
+            evicted.expires(),
+            Some(Expiration::DateTime(date))
+                if date == DateTime::parse_from_rfc3339("2100-01-01T12:00:00Z").unwrap()
+        ));
+
+        assert_eq!(jar.len(), 2);
+        assert!(jar.get("a").is_some());
+        assert!(jar.get("c").is_some());
+    }
+
+    #[test]
+    fn limit_evicts_by_max_age_and_expires() {
+        let mut jar = CookieJar::with_limit(3);
+
+        let far = Cookie::parse("far=1; Expires=Fri, 01 Jan 2100 12:00:00 GMT").unwrap();
+        let session = Cookie::parse("session=2").unwrap();
+        // Max-Age wins over the far away Expires
+        let brief =
+            Cookie::parse("brief=3; Max-Age=60; Expires=Fri, 01 Jan 2100 12:00:00 GMT").unwrap();
+        let hour = Cookie::parse("hour=4; Max-Age=3600").unwrap();
+
+        jar.add(far.into_owned());
+        jar.add(session.into_owned());
+        jar.add(brief.into_owned());
+
+        let evicted = jar.add(hour.into_owned()).unwrap();
+        assert_eq!(evicted.name(), "session");
+
+        let evicted = jar.add(Cookie::parse("x=5; Max-Age=7200").unwrap().into_owned());
+        assert_eq!(evicted.unwrap().name(), "brief");
+
+        let evicted = jar.add(Cookie::parse("y=6; Max-Age=0").unwrap().into_owned());
+        assert_eq!(evicted.unwrap().name(), "y");
+
+        assert_eq!(
+            jar.iter().map(|c| c.name()).collect::<Vec<_>>(),
+            ["far", "hour", "x"]
+        );
+    }
+
+    #[test]
+    fn limit_evicts_oldest_without_expiry() {
+        let mut jar = CookieJar::with_limit(2);
+
+        jar.add(Cookie::parse("a=1").unwrap().into_owned());
+        jar.add(Cookie::parse("b=2").unwrap().into_owned());
+        jar.add(Cookie::parse("a=3").unwrap().into_owned());
+
+        let evicted = jar.add(Cookie::parse("c=4").unwrap().into_owned()).unwrap();
+        assert_eq!(evicted.name_value(), ("b", "2"));
+        assert_eq!(jar.get("a").map(|c| c.value()), Some("3"));
+    }
+}
@@ -1,8 +1,29 @@
-pub use cookie::Cookie;
+pub use builder::CookieBuilder;
+pub use cookie::{Cookie, removal_header};
+pub use display::{EncodedDisplay, ForwardDisplay, RequestDisplay};
+pub use expires::Expiration;
+pub use jar::CookieJar;
+pub use parse::{
+    ParseError, ParseLimits, ParseMode, ParseOptions, ParseResult, ParseWarning, ParsedHeader,
+};
+pub use parts::CookieParts;
+pub use priority::Priority;
+pub use same_site::SameSite;
+pub use validation::{ValidationIssue, is_valid_cookie_name, is_valid_cookie_value};
 
 mod builder;
+mod cookie;
+mod display;
+mod expires;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "idna")]
+mod idna;
+mod jar;
 mod parse;
+mod parts;
+mod priority;
 mod same_site;
-mod expires;
-mod cookie;
-
+#[cfg(feature = "serde")]
+mod serde;
+mod validation;
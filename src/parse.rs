@@ -1,31 +1,393 @@
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, ffi::OsStr, fmt::Display, ops::Range, time::Duration};
 
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use percent_encoding::percent_decode_str;
 
-use crate::{Cookie, cookie::CookieStr, expires::Expiration, same_site::SameSite};
+use crate::{
+    Cookie,
+    cookie::{CookieStr, is_ip_literal},
+    expires::Expiration,
+    priority::Priority,
+    same_site::SameSite,
+    validation::ValidationIssue,
+};
 
-const FMT1: &'static str = "%a, %d %b %Y %H:%M:%S GMT";
-const FMT2: &'static str = "%A, %d-%b-%y %H:%M:%S GMT";
-const FMT3: &'static str = "%a, %b %-d %H:%M:%S %-Y"; // Can't handle padding with spaces. Only with zeroes or nothing
-const FMT4: &'static str = "%a, %d-%b-%-Y %H:%M:%S GMT";
+const FMT1: &str = "%a, %d %b %Y %H:%M:%S GMT";
+const FMT2: &str = "%A, %d-%b-%y %H:%M:%S GMT";
+const FMT3: &str = "%a, %b %-d %H:%M:%S %-Y"; // Can't handle padding with spaces. Only with zeroes or nothing
+const FMT4: &str = "%a, %d-%b-%-Y %H:%M:%S GMT";
 
 pub type ParseResult<T> = Result<T, ParseError>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Accepts common real-world deviations, e.g. `Max-Age=3600s` or
+    /// fractional seconds in `Expires`.
+    Lenient,
+    /// Ignores attributes with malformed values, and reads `Expires` with the
+    /// RFC 6265 date algorithm browsers use.
+    #[default]
+    Standard,
+    /// Rejects attributes with malformed values.
+    Strict,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseLimits {
+    /// Maximum length of the whole input in bytes.
+    pub max_len: Option<usize>,
+    /// Maximum length of any single attribute value in bytes.
+    pub max_attr_value_len: Option<usize>,
+    /// Maximum number of pairs accepted from a `Cookie` request header.
+    pub max_cookies: Option<usize>,
+}
+
+impl ParseLimits {
+    /// The limits request headers are parsed with when none are given, e.g.
+    /// by `detect_and_parse`: at most 180 pairs, as many cookies as a browser
+    /// keeps for one domain.
+    pub fn request() -> Self {
+        ParseLimits {
+            max_cookies: Some(180),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    pub mode: ParseMode,
+    pub limits: ParseLimits,
+    /// Percent-decodes the name and value, `name_raw()` and `value_raw()`
+    /// keep the encoded forms.
+    pub percent_decode: bool,
+    /// Stores `SameSite=Lax` when the attribute is absent, instead of leaving
+    /// it unspecified.
+    pub default_same_site_lax: bool,
+    /// Rejects cookies with `SameSite=None` that aren't `Secure`.
+    pub enforce_same_site_secure: bool,
+    /// Keeps the whitespace around the value in `value_raw()`. `value()` is
+    /// trimmed either way.
+    pub preserve_value_whitespace: bool,
+    /// Rejects `__Secure-` and `__Host-` cookies that break the constraints
+    /// of their prefix, see `Cookie::validate_prefix`.
+    pub enforce_prefixes: bool,
+    /// Only accepts `Expires` in the RFC 1123 form RFC 6265 servers must
+    /// send, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+    pub rfc1123_dates_only: bool,
+    /// Rejects control characters anywhere in the input and anything
+    /// `Cookie::validate` reports.
+    pub validate_grammar: bool,
+    /// Accepts the non-standard `Expires=@1700000000` form, in seconds since
+    /// the Unix epoch. Only honored in lenient mode without
+    /// `rfc1123_dates_only`.
+    pub epoch_expires: bool,
+}
+
+impl ParseOptions {
+    pub fn lenient() -> Self {
+        ParseOptions {
+            mode: ParseMode::Lenient,
+            ..Default::default()
+        }
+    }
+
+    pub fn strict() -> Self {
+        ParseOptions {
+            mode: ParseMode::Strict,
+            ..Default::default()
+        }
+    }
+
+    /// Rejects everything that doesn't conform to the RFC 6265 server
+    /// grammar. Meant for conformance testing rather than real-world headers.
+    pub fn rfc6265_strict() -> Self {
+        ParseOptions {
+            mode: ParseMode::Strict,
+            rfc1123_dates_only: true,
+            validate_grammar: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn encoded() -> Self {
+        ParseOptions {
+            percent_decode: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// The cookie's `Expires` is in the past or its `Max-Age` is zero.
+    AlreadyExpired,
+    /// An attribute like `=foo` had a value but no name and was dropped.
+    EmptyAttributeName,
+    /// `SameSite=` had no value. Like browsers, the cookie is parsed with
+    /// `SameSite` unspecified, even in strict mode.
+    EmptySameSite,
+}
+
+#[derive(Debug)]
+pub enum ParsedHeader<'a> {
+    /// A single `Set-Cookie` value with its attributes.
+    Response(Box<Cookie<'a>>),
+    /// The `name=value` pairs of a `Cookie` request header.
+    Request(Vec<Cookie<'a>>),
+}
+
+const SET_COOKIE_ATTRIBUTES: [&str; 9] = [
+    "Expires",
+    "Max-Age",
+    "Domain",
+    "Path",
+    "Secure",
+    "HttpOnly",
+    "SameSite",
+    "Partitioned",
+    "Priority",
+];
+
 pub fn parse_cookie<'a, T: Into<Cow<'a, str>>>(s: T) -> ParseResult<Cookie<'a>> {
+    parse_cookie_with_options(s, ParseOptions::default())
+}
+
+pub fn parse_cookie_with_options<'a, T: Into<Cow<'a, str>>>(
+    s: T,
+    options: ParseOptions,
+) -> ParseResult<Cookie<'a>> {
+    parse_cookie_filtered(s, options, None).map(|(cookie, _)| cookie)
+}
+
+/// Parses `s` like `parse_cookie`, also reporting anything suspicious that
+/// didn't prevent parsing.
+pub fn parse_cookie_with_warnings<'a, T: Into<Cow<'a, str>>>(
+    s: T,
+) -> ParseResult<(Cookie<'a>, Vec<ParseWarning>)> {
+    let (cookie, mut warnings) = parse_cookie_filtered(s, ParseOptions::default(), None)?;
+
+    // Reads the clock, so only done when warnings are asked for
+    if cookie.is_expired_at(Utc::now()) {
+        warnings.push(ParseWarning::AlreadyExpired);
+    }
+
+    Ok((cookie, warnings))
+}
+
+pub fn parse_cookie_with_limits<'a, T: Into<Cow<'a, str>>>(
+    s: T,
+    limits: ParseLimits,
+) -> ParseResult<Cookie<'a>> {
+    let options = ParseOptions {
+        limits,
+        ..Default::default()
+    };
+    parse_cookie_with_options(s, options)
+}
+
+/// Parses `s` keeping only the attributes named in `allowed` (compared
+/// case-insensitively). Every other attribute, known or not, is ignored.
+pub fn parse_cookie_with_allowed_attrs<'a, T: Into<Cow<'a, str>>>(
+    s: T,
+    allowed: &[&str],
+) -> ParseResult<Cookie<'a>> {
+    parse_cookie_filtered(s, ParseOptions::default(), Some(allowed)).map(|(cookie, _)| cookie)
+}
+
+fn parse_cookie_filtered<'a, T: Into<Cow<'a, str>>>(
+    s: T,
+    options: ParseOptions,
+    allowed: Option<&[&str]>,
+) -> ParseResult<(Cookie<'a>, Vec<ParseWarning>)> {
     let str = s.into();
-    let mut cookie = parse_inner(&str)?;
+    let mut scratch = String::new();
+    let parsed = parse_inner(&str, options, allowed, &mut scratch)?;
 
+    let mut cookie = parsed.cookie;
+    if parsed.owned_val.is_some() {
+        cookie.val = CookieStr::Concrete(Cow::Owned(scratch));
+    }
     cookie.cookie_string = Some(str);
 
+    if options.validate_grammar {
+        // HTAB is allowed as whitespace around names, values and attributes
+        if cookie_string_has_control(&cookie) {
+            return Err(ParseError::ControlCharacter);
+        }
+        cookie.validate()?;
+    }
+
+    if options.enforce_prefixes {
+        cookie
+            .validate_prefix()
+            .map_err(ParseError::PrefixViolation)?;
+    }
+
+    Ok((cookie, parsed.warnings))
+}
+
+fn cookie_string_has_control(cookie: &Cookie) -> bool {
+    cookie
+        .cookie_string
+        .as_ref()
+        .is_some_and(|s| s.bytes().any(|c| c.is_ascii_control() && c != b'\t'))
+}
+
+pub fn parse_cookie_os(s: &OsStr) -> ParseResult<Cookie<'static>> {
+    let s = s.to_str().ok_or(ParseError::Utf8Error)?;
+    parse_cookie(s.to_owned())
+}
+
+/// Parses the first line of `s`, returning the cookie and everything after the
+/// line break. Lines end at `\r\n`, a bare `\n` is accepted too. Without a line
+/// break the whole input is parsed and the remainder is empty.
+pub fn parse_cookie_line(s: &str) -> ParseResult<(Cookie<'_>, &str)> {
+    let (line, rest) = match s.find('\n') {
+        Some(idx) => (&s[..idx], &s[(idx + 1)..]),
+        None => (s, ""),
+    };
+    let line = line.strip_suffix('\r').unwrap_or(line);
+
+    Ok((parse_cookie(line)?, rest))
+}
+
+/// Parses every line of a raw header dump, stripping a leading `Set-Cookie:`
+/// from each. Blank lines are skipped, and a failed line doesn't stop the
+/// ones after it.
+pub fn parse_set_cookie_block(s: &str) -> Vec<ParseResult<Cookie<'_>>> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let line = match line.split_once(':') {
+                Some((name, value)) if name.trim().eq_ignore_ascii_case("Set-Cookie") => value,
+                _ => line,
+            };
+            parse_cookie(line)
+        })
+        .collect()
+}
+
+/// Parses `s` writing every owned transformation of it (like unescaping a
+/// quoted value) into `scratch` instead of a fresh allocation.
+///
+/// `scratch` is cleared before parsing and stays mutably borrowed for as long
+/// as the returned cookie lives, so the cookie has to be dropped (or turned
+/// into an owned one) before the buffer can be reused. Any previous contents
+/// of `scratch` are lost.
+pub fn parse_cookie_into<'a>(s: &'a str, scratch: &'a mut String) -> ParseResult<Cookie<'a>> {
+    scratch.clear();
+    let parsed = parse_inner(s, ParseOptions::default(), None, scratch)?;
+    let scratch: &'a String = scratch;
+
+    let mut cookie = parsed.cookie;
+    if let Some(range) = parsed.owned_val {
+        cookie.val = CookieStr::Concrete(Cow::Borrowed(&scratch[range]));
+    }
+    cookie.cookie_string = Some(Cow::Borrowed(s));
+
     Ok(cookie)
 }
 
-fn parse_inner<'a>(s: &str) -> Result<Cookie<'a>, ParseError> {
-    let mut attributes = s.split(';');
+/// Guesses whether `s` is a `Set-Cookie` or a `Cookie` header value.
+///
+/// If any segment after the first `;` is named like a known `Set-Cookie`
+/// attribute (`Path`, `Secure`, ...) the input is parsed as a single response
+/// cookie, otherwise every segment is treated as a request `name=value` pair.
+/// The guess is ambiguous: a request cookie that happens to be named `Path`
+/// is read as an attribute, and a response cookie with only unknown
+/// attributes is read as several request cookies. Request headers are parsed
+/// with `ParseLimits::request()`.
+pub fn detect_and_parse(s: &str) -> ParseResult<ParsedHeader<'_>> {
+    let is_response = s.split(';').skip(1).any(|attr| {
+        let key = attr.split('=').next().unwrap_or_default().trim();
+        SET_COOKIE_ATTRIBUTES
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(key))
+    });
 
-    let name_val = attributes.next().expect("Name and Value are Some");
+    if is_response {
+        parse_cookie(s).map(|cookie| ParsedHeader::Response(Box::new(cookie)))
+    } else {
+        parse_request_with_limits(s, ParseLimits::request()).map(ParsedHeader::Request)
+    }
+}
+
+/// Parses the `name=value` pairs of a `Cookie` request header, skipping
+/// malformed ones. Fails with `TooManyCookies` when the header has more than
+/// `max_cookies` non-blank segments, malformed ones included, before parsing
+/// any of them.
+pub fn parse_request_with_limits(s: &str, limits: ParseLimits) -> ParseResult<Vec<Cookie<'_>>> {
+    if limits.max_len.is_some_and(|max| s.len() > max) {
+        return Err(ParseError::TooLong);
+    }
 
-    let (name, val) = match name_val.find('=') {
-        Some(idx) => (name_val[..idx].trim(), name_val[(idx + 1)..].trim()),
+    if let Some(max) = limits.max_cookies {
+        let mut segments = s.split(';').filter(|pair| !pair.trim().is_empty());
+        if segments.nth(max).is_some() {
+            return Err(ParseError::TooManyCookies);
+        }
+    }
+
+    Ok(parse_request_cookies(s).filter_map(Result::ok).collect())
+}
+
+/// Parses a `Cookie` request header, where every `;`-separated segment is a
+/// `name=value` pair of its own and there are no attributes. Blank segments
+/// are skipped, a segment without `=` yields `MissingPair` and one with an
+/// empty name `EmptyName`.
+///
+/// RFC 2965 `$`-prefixed pseudo-attributes aren't cookies: `$Path` and
+/// `$Domain` are attached to the cookie before them, `$Version` and any other
+/// `$` name are dropped.
+pub fn parse_request_cookies(s: &str) -> impl Iterator<Item = ParseResult<Cookie<'_>>> {
+    let mut segments = s
+        .split(';')
+        .filter(|pair| !pair.trim().is_empty())
+        .peekable();
+
+    std::iter::from_fn(move || {
+        loop {
+            let (name, val) = match split_request_pair(segments.next()?) {
+                Ok(pair) => pair,
+                Err(err) => return Some(Err(err)),
+            };
+            if name.starts_with('$') {
+                continue;
+            }
+
+            let name = CookieStr::indexed(name, s).expect("Name in bounds of s");
+            let val = CookieStr::indexed(val, s).expect("Val in bounds of s");
+
+            let mut cookie = Cookie::from_name_value(name, val);
+            cookie.cookie_string = Some(Cow::Borrowed(s));
+
+            while let Some(attr) = segments.next_if(|pair| pair.trim_start().starts_with('$')) {
+                let Ok((key, val)) = split_request_pair(attr) else {
+                    continue;
+                };
+                let val = val
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(val);
+                let val = Some(CookieStr::indexed(val, s).expect("Val in bounds of s"));
+
+                if key.eq_ignore_ascii_case("$Path") {
+                    cookie.path = val;
+                } else if key.eq_ignore_ascii_case("$Domain") {
+                    cookie.domain = val;
+                }
+            }
+
+            return Some(Ok(cookie));
+        }
+    })
+}
+
+fn split_request_pair(pair: &str) -> ParseResult<(&str, &str)> {
+    let (name, val) = match pair.find('=') {
+        Some(idx) => (pair[..idx].trim(), pair[(idx + 1)..].trim()),
         None => return Err(ParseError::MissingPair),
     };
 
@@ -33,53 +395,156 @@ fn parse_inner<'a>(s: &str) -> Result<Cookie<'a>, ParseError> {
         return Err(ParseError::EmptyName);
     }
 
-    let name = CookieStr::indexed(name, s).expect("Name in bounds of s");
-    let val = CookieStr::indexed(val, s).expect("Val in bounds of s");
-
-    let mut cookie = Cookie {
-        cookie_string: None,
-        name,
-        val,
-        expires: None,
-        max_age: None,
-        domain: None,
-        path: None,
-        secure: None,
-        http_only: None,
-        same_site: None,
+    Ok((name, val))
+}
+
+struct Parsed<'a> {
+    cookie: Cookie<'a>,
+    // Range of the scratch buffer holding the unescaped or decoded value
+    owned_val: Option<Range<usize>>,
+    warnings: Vec<ParseWarning>,
+}
+
+fn parse_inner<'a>(
+    s: &str,
+    options: ParseOptions,
+    allowed: Option<&[&str]>,
+    scratch: &mut String,
+) -> Result<Parsed<'a>, ParseError> {
+    if options.limits.max_len.is_some_and(|max| s.len() > max) {
+        return Err(ParseError::TooLong);
+    }
+
+    let name_val_end = name_value_end(s);
+    let name_val = &s[..name_val_end];
+    // Skips the `;` ending the pair, an empty remainder yields no attributes
+    let attributes = s[name_val_end..].split(';').skip(1);
+
+    let (name, untrimmed_val) = match name_val.find('=') {
+        Some(idx) => (name_val[..idx].trim(), &name_val[(idx + 1)..]),
+        None => return Err(ParseError::MissingPair),
+    };
+    let val = untrimmed_val.trim();
+
+    if name.is_empty() {
+        return Err(ParseError::EmptyName);
+    }
+
+    let name_raw = CookieStr::indexed(name, s).expect("Name in bounds of s");
+    let (name, name_raw) = if options.percent_decode && name.contains('%') {
+        let decoded = percent_decode_str(name)
+            .decode_utf8()
+            .map_err(|_| ParseError::Utf8Error)?;
+        (
+            CookieStr::Concrete(Cow::Owned(decoded.into_owned())),
+            Some(name_raw),
+        )
+    } else {
+        (name_raw, None)
+    };
+
+    // A quoted value followed by junk (`"abc"junk`) is an error in strict mode,
+    // has the junk ignored in lenient mode and is kept verbatim otherwise
+    let unquoted = match unquote(val) {
+        Some((inner, "")) => Some(inner),
+        Some((inner, _)) => match options.mode {
+            ParseMode::Strict => return Err(ParseError::TrailingData),
+            ParseMode::Lenient => Some(inner),
+            ParseMode::Standard => None,
+        },
+        None => None,
+    };
+
+    let unquoted_val = unquoted.unwrap_or(val);
+    let needs_unescape = unquoted.is_some_and(|v| v.contains('\\'));
+    let needs_decode = options.percent_decode && unquoted_val.contains('%');
+
+    let mut owned_val = None;
+    let cookie_val = if needs_unescape || needs_decode {
+        let start = scratch.len();
+        if needs_unescape {
+            unescape_into(unquoted_val, scratch);
+        } else {
+            scratch.push_str(unquoted_val);
+        }
+
+        if needs_decode {
+            let decoded = percent_decode_str(&scratch[start..])
+                .decode_utf8()
+                .map_err(|_| ParseError::Utf8Error)?
+                .into_owned();
+            scratch.truncate(start);
+            scratch.push_str(&decoded);
+        }
+
+        owned_val = Some(start..scratch.len());
+
+        // Filled in from the scratch buffer by the caller
+        CookieStr::Concrete(Cow::Borrowed(""))
+    } else {
+        CookieStr::indexed(unquoted_val, s).expect("Val in bounds of s")
     };
 
+    let mut cookie = Cookie::from_name_value(name, cookie_val);
+    cookie.name_raw = name_raw;
+    if options.preserve_value_whitespace {
+        cookie.val_raw = Some(CookieStr::indexed(untrimmed_val, s).expect("Val in bounds of s"));
+    } else if unquoted.is_some() || owned_val.is_some() {
+        cookie.val_raw = Some(CookieStr::indexed(val, s).expect("Val in bounds of s"));
+    }
+
+    let mut warnings = Vec::new();
     for attr in attributes {
         let (key, val) = match attr.find('=') {
             Some(idx) => (attr[0..idx].trim(), Some(attr[(idx + 1)..].trim())),
             None => (attr.trim(), None),
         };
 
+        if allowed.is_some_and(|allowed| !allowed.iter().any(|a| a.eq_ignore_ascii_case(key))) {
+            continue;
+        }
+
+        if let (Some(max), Some(val)) = (options.limits.max_attr_value_len, val)
+            && val.len() > max
+        {
+            return Err(ParseError::AttributeTooLong);
+        }
+
+        // Attribute names are case-insensitive, match on the canonical spelling
+        let key = SET_COOKIE_ATTRIBUTES
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(key))
+            .copied()
+            .unwrap_or(key);
+
         match (key, val) {
             ("Expires", Some(expires)) => {
-                cookie.expires = Some(Expiration::DateTime(parse_date_all_formats(expires)?))
+                let epoch = expires
+                    .strip_prefix('@')
+                    .filter(|_| options.epoch_expires && options.mode == ParseMode::Lenient);
+                let date = match (epoch, options.rfc1123_dates_only) {
+                    (_, true) => parse_date(expires, FMT1)?,
+                    (Some(secs), false) => {
+                        parse_epoch(secs).ok_or_else(|| invalid_date(expires))?
+                    }
+                    (None, false) => parse_expires(expires, options.mode)?,
+                };
+                cookie.expires = Some(Expiration::DateTime(date));
+                cookie.expires_raw =
+                    Some(CookieStr::indexed(expires, s).expect("Expires in bounds of s"));
             }
             ("Max-Age", Some(max_age)) => {
-                cookie.max_age = {
-                    let is_negatove = max_age.starts_with('-');
-                    let max_age = if is_negatove { &max_age[1..] } else { max_age };
-
-                    if !max_age.chars().all(|c| c.is_digit(10)) {
-                        continue;
-                    }
-
-                    if is_negatove {
-                        Some(Duration::ZERO)
-                    } else {
-                        Some(
-                            max_age
-                                .parse::<u64>()
-                                .map(Duration::from_secs)
-                                .unwrap_or_else(|_| Duration::from_secs(u64::MAX)),
-                        )
-                    }
+                if let Some(parsed) = parse_max_age(max_age, options.mode)? {
+                    cookie.max_age = Some(parsed);
+                    cookie.max_age_raw =
+                        Some(CookieStr::indexed(max_age, s).expect("Max-Age in bounds of s"));
                 }
             }
+            // An IP literal can't be a `Domain`, browsers keep the cookie host-only
+            ("Domain", Some(domain)) if is_ip_literal(domain) => match options.mode {
+                ParseMode::Strict => return Err(ParseError::InvalidDomain),
+                ParseMode::Lenient | ParseMode::Standard => {}
+            },
             ("Domain", Some(domain)) => {
                 cookie.domain = Some(CookieStr::indexed(domain, s).expect("Domain in bounds of s"))
             }
@@ -88,58 +553,413 @@ fn parse_inner<'a>(s: &str) -> Result<Cookie<'a>, ParseError> {
             }
             ("Secure", _) => cookie.secure = Some(true),
             ("HttpOnly", _) => cookie.http_only = Some(true),
+            ("Partitioned", _) => cookie.partitioned = Some(true),
             ("SameSite", Some(same_site)) => {
-                if same_site.eq_ignore_ascii_case("strict") {
-                    cookie.same_site = Some(SameSite::Strict)
-                } else if same_site.eq_ignore_ascii_case("lax") {
-                    cookie.same_site = Some(SameSite::Lax)
-                } else if same_site.eq_ignore_ascii_case("none") {
-                    cookie.same_site = Some(SameSite::None)
-                } else {
-                    // Do nothing
+                let token = same_site
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .unwrap_or(same_site)
+                    .trim();
+
+                match (token.parse::<SameSite>(), options.mode) {
+                    _ if token.is_empty() => warnings.push(ParseWarning::EmptySameSite),
+                    (Ok(parsed), _) => {
+                        cookie.same_site = Some(parsed);
+                        cookie.same_site_raw = Some(
+                            CookieStr::indexed(same_site, s).expect("SameSite in bounds of s"),
+                        );
+                    }
+                    (Err(err), ParseMode::Strict) => return Err(err),
+                    (Err(_), ParseMode::Lenient | ParseMode::Standard) => {}
                 }
             }
+            ("Priority", Some(priority)) => match (priority.parse::<Priority>(), options.mode) {
+                (Ok(parsed), _) => cookie.priority = Some(parsed),
+                (Err(err), ParseMode::Strict) => return Err(err),
+                (Err(_), ParseMode::Lenient | ParseMode::Standard) => {}
+            },
+            ("", Some(_)) => warnings.push(ParseWarning::EmptyAttributeName),
+            (key, val) if !key.is_empty() && !SET_COOKIE_ATTRIBUTES.contains(&key) => {
+                let key = CookieStr::indexed(key, s).expect("Key in bounds of s");
+                let val = val.map(|v| CookieStr::indexed(v, s).expect("Val in bounds of s"));
+                cookie.extensions.push((key, val));
+            }
             _ => {}
         }
     }
 
-    Ok(cookie)
+    if options.default_same_site_lax && cookie.same_site.is_none() {
+        cookie.same_site = Some(SameSite::Lax);
+    }
+
+    if options.enforce_same_site_secure
+        && cookie.same_site == Some(SameSite::None)
+        && cookie.secure != Some(true)
+    {
+        return Err(ParseError::SameSiteNoneNotSecure);
+    }
+
+    Ok(Parsed {
+        cookie,
+        owned_val,
+        warnings,
+    })
+}
+
+// Splits a quoted value into its contents and whatever follows the closing quote
+// End of the `name=value` pair. A `;` inside a quoted value doesn't end it,
+// so a quoted `a;b` survives a round trip through `Display`
+fn name_value_end(s: &str) -> usize {
+    let semi = s.find(';').unwrap_or(s.len());
+    let Some(eq) = s[..semi].find('=') else {
+        return semi;
+    };
+
+    match unquote(s[(eq + 1)..].trim_start()) {
+        Some((_, rest)) => {
+            let rest_start = s.len() - rest.len();
+            rest.find(';').map_or(s.len(), |idx| rest_start + idx)
+        }
+        None => semi,
+    }
+}
+
+fn unquote(val: &str) -> Option<(&str, &str)> {
+    let inner = val.strip_prefix('"')?;
+    let mut chars = inner.char_indices();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return Some((&inner[..idx], &inner[(idx + 1)..])),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// Resolves the quoted-pairs (`\"`, `\\`) of an HTTP quoted-string
+fn unescape_into(val: &str, out: &mut String) {
+    let mut chars = val.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+}
+
+fn parse_max_age(max_age: &str, mode: ParseMode) -> ParseResult<Option<Duration>> {
+    let is_negative = max_age.starts_with('-');
+    let max_age = if is_negative { &max_age[1..] } else { max_age };
+
+    let digits = match mode {
+        // Take the leading digit run and ignore trailing junk like `3600s`.
+        // Underscores between digits are separators, as in `3_600`
+        ParseMode::Lenient if !max_age.starts_with('_') => {
+            let end = max_age
+                .find(|c: char| !c.is_ascii_digit() && c != '_')
+                .unwrap_or(max_age.len());
+            let run = max_age[..end].trim_end_matches('_');
+            Cow::Owned(run.replace('_', ""))
+        }
+        ParseMode::Lenient => Cow::Borrowed(""),
+        ParseMode::Standard | ParseMode::Strict => Cow::Borrowed(max_age),
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return match mode {
+            ParseMode::Strict => Err(ParseError::InvalidMaxAge),
+            ParseMode::Lenient | ParseMode::Standard => Ok(None),
+        };
+    }
+
+    if is_negative {
+        return Ok(Some(Duration::ZERO));
+    }
+
+    Ok(Some(
+        digits
+            .parse::<u64>()
+            .map(Duration::from_secs)
+            .unwrap_or_else(|_| Duration::from_secs(u64::MAX)),
+    ))
+}
+
+// Strict mode sticks to the fixed formats, the others fall back to the RFC 6265
+// algorithm, e.g. for asctime dates. Fractional seconds stay lenient-only
+fn parse_expires(expires: &str, mode: ParseMode) -> ParseResult<DateTime<Utc>> {
+    match mode {
+        ParseMode::Lenient | ParseMode::Standard => parse_date_all_formats(expires)
+            .or_else(|_| parse_date_rfc6265(expires, mode == ParseMode::Lenient)),
+        ParseMode::Strict => parse_date_all_formats(expires),
+    }
+}
+
+fn parse_epoch(secs: &str) -> Option<DateTime<Utc>> {
+    secs.parse::<i64>()
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
 }
 
 fn parse_date(str: &str, fmt: &str) -> Result<DateTime<Utc>, ParseError> {
-    let date =
-        NaiveDateTime::parse_from_str(str.trim(), fmt).map_err(|_| ParseError::InvalidDate)?;
+    let date = NaiveDateTime::parse_from_str(str.trim(), fmt).map_err(|_| invalid_date(str))?;
 
     Ok(DateTime::from_naive_utc_and_offset(date, Utc))
 }
 
 fn parse_date_all_formats(str: &str) -> Result<DateTime<Utc>, ParseError> {
     let date = str.trim();
-    let date = parse_date(date, FMT1)
+    parse_date(date, FMT1)
         .or_else(|_| parse_date(date, FMT2))
         .or_else(|_| parse_date(date, FMT3))
         .or_else(|_| parse_date(date, FMT4))
-        .map_err(|_| ParseError::InvalidDate);
+        .map_err(|_| invalid_date(str))
+}
+
+/// The cookie-date algorithm of RFC 6265, section 5.1.1. The input is split
+/// on delimiters and each token is tried as a time, day of month, month and
+/// year, in that order, taking the first match of each. Anything else, like
+/// the weekday or the time zone, is ignored. Seconds with a fractional part,
+/// e.g. `08:49:37.5`, are only accepted with `fractional_seconds`.
+fn parse_date_rfc6265(str: &str, fractional_seconds: bool) -> Result<DateTime<Utc>, ParseError> {
+    if !fractional_seconds && has_fractional_seconds(str) {
+        return Err(invalid_date(str));
+    }
+
+    let mut time = None;
+    let mut day = None;
+    let mut month = None;
+    let mut year = None;
+
+    let tokens = str
+        .as_bytes()
+        .split(|&c| is_date_delimiter(c))
+        .filter(|token| !token.is_empty());
+
+    for token in tokens {
+        if time.is_none()
+            && let Some(parsed) = parse_time_token(token)
+        {
+            time = Some(parsed);
+        } else if day.is_none()
+            && let Some(parsed) = parse_digits_token(token, 1, 2)
+        {
+            day = Some(parsed);
+        } else if month.is_none()
+            && let Some(parsed) = parse_month_token(token)
+        {
+            month = Some(parsed);
+        } else if year.is_none()
+            && let Some(parsed) = parse_digits_token(token, 2, 4)
+        {
+            year = Some(parsed);
+        }
+    }
+
+    let (Some((hour, min, sec)), Some(day), Some(month), Some(year)) = (time, day, month, year)
+    else {
+        return Err(invalid_date(str));
+    };
+
+    let year = match year {
+        70..=99 => year + 1900,
+        0..=69 => year + 2000,
+        year => year,
+    };
+    if year < 1601 || hour > 23 || min > 59 || sec > 59 {
+        return Err(invalid_date(str));
+    }
 
-    date
+    NaiveDate::from_ymd_opt(year as i32, month, day)
+        .and_then(|date| date.and_hms_opt(hour, min, sec))
+        .map(|date| DateTime::from_naive_utc_and_offset(date, Utc))
+        .ok_or_else(|| invalid_date(str))
+}
+
+fn invalid_date(input: &str) -> ParseError {
+    ParseError::InvalidDate {
+        input: input.to_owned(),
+    }
+}
+
+fn is_date_delimiter(c: u8) -> bool {
+    matches!(c, 0x09 | 0x20..=0x2F | 0x3B..=0x40 | 0x5B..=0x60 | 0x7B..=0x7E)
+}
+
+// `min` to `max` digits, optionally followed by a non-digit and anything else
+fn parse_digits_token(token: &[u8], min: usize, max: usize) -> Option<u32> {
+    split_digits(token, min, max).map(|(value, _)| value)
+}
+
+fn split_digits(token: &[u8], min: usize, max: usize) -> Option<(u32, &[u8])> {
+    let len = token.iter().take_while(|c| c.is_ascii_digit()).count();
+    if len < min || len > max {
+        return None;
+    }
+
+    let value = token[..len]
+        .iter()
+        .fold(0, |acc, c| acc * 10 + u32::from(c - b'0'));
+    Some((value, &token[len..]))
+}
+
+// hh:mm:ss with one or two digits each, e.g. `8:49:37`
+fn parse_time_token(token: &[u8]) -> Option<(u32, u32, u32)> {
+    let (hour, rest) = split_digits(token, 1, 2)?;
+    let (min, rest) = split_digits(rest.strip_prefix(b":")?, 1, 2)?;
+    let (sec, _) = split_digits(rest.strip_prefix(b":")?, 1, 2)?;
+
+    Some((hour, min, sec))
+}
+
+// `:ss.f`, i.e. seconds followed by a fraction, like in `07:28:00.123`
+fn has_fractional_seconds(str: &str) -> bool {
+    str.as_bytes().windows(5).any(|window| {
+        matches!(window, [b':', s1, s2, b'.', f] if s1.is_ascii_digit()
+            && s2.is_ascii_digit()
+            && f.is_ascii_digit())
+    })
+}
+
+fn parse_month_token(token: &[u8]) -> Option<u32> {
+    const MONTHS: [&[u8]; 12] = [
+        b"jan", b"feb", b"mar", b"apr", b"may", b"jun", b"jul", b"aug", b"sep", b"oct", b"nov",
+        b"dec",
+    ];
+
+    let prefix = token.get(..3)?;
+    MONTHS
+        .iter()
+        .position(|month| month.eq_ignore_ascii_case(prefix))
+        .map(|idx| idx as u32 + 1)
 }
 
 #[derive(Debug, Clone)]
 pub enum ParseError {
+    /// A `Set-Cookie` string or a pair of a `Cookie` request header has no `=`
+    /// between the name and the value.
     MissingPair,
+    /// The clearer name for `MissingPair`. Parsing still returns `MissingPair`
+    /// so existing matches keep working; the switch to this variant is left
+    /// for the next breaking release.
+    NoNameValueSeparator,
     EmptyName,
     InvalidMaxAge,
     InvalidSameSite,
-    InvalidDate,
+    InvalidPriority,
+    /// `Domain` is an IP address, which only strict parsing rejects.
+    InvalidDomain,
+    /// `Expires` isn't a date in any format the parse mode accepts.
+    InvalidDate {
+        /// The attribute value as it appeared in the input.
+        input: String,
+    },
     Utf8Error,
+    TrailingData,
+    /// The name isn't an RFC 6265 token.
+    InvalidName,
+    /// The value has characters outside the RFC 6265 cookie-value grammar.
+    InvalidValue,
+    /// A control character outside of whitespace, only rejected when
+    /// validating the grammar.
+    ControlCharacter,
+    SameSiteNoneNotSecure,
+    TooLong,
+    AttributeTooLong,
+    TooManyCookies,
+    PrefixViolation(ValidationIssue),
 }
 
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingPair => f.write_str("cookie string is missing a name=value pair"),
+            ParseError::NoNameValueSeparator => {
+                f.write_str("cookie string has no '=' between name and value")
+            }
+            ParseError::EmptyName => f.write_str("cookie name is empty"),
+            ParseError::InvalidMaxAge => f.write_str("Max-Age is not a number of seconds"),
+            ParseError::InvalidSameSite => {
+                f.write_str("SameSite is not one of Strict, Lax or None")
+            }
+            ParseError::InvalidPriority => {
+                f.write_str("Priority is not one of Low, Medium or High")
+            }
+            ParseError::InvalidDomain => f.write_str("Domain is an IP address"),
+            ParseError::InvalidDate { input } => {
+                write!(f, "Expires is not a recognized date: {input:?}")
+            }
+            ParseError::Utf8Error => f.write_str("cookie string is not valid UTF-8"),
+            ParseError::InvalidName => {
+                f.write_str("cookie name contains a control, whitespace or separator character")
+            }
+            ParseError::InvalidValue => f.write_str("cookie value contains an invalid character"),
+            ParseError::ControlCharacter => {
+                f.write_str("cookie string contains a control character")
+            }
+            ParseError::TrailingData => f.write_str("unexpected data after a quoted value"),
+            ParseError::SameSiteNoneNotSecure => {
+                f.write_str("SameSite=None cookie is missing the Secure attribute")
+            }
+            ParseError::TooLong => f.write_str("cookie string exceeds the length limit"),
+            ParseError::AttributeTooLong => f.write_str("attribute value exceeds the length limit"),
+            ParseError::TooManyCookies => {
+                f.write_str("request header holds more cookies than the limit")
+            }
+            ParseError::PrefixViolation(issue) => write!(f, "cookie name prefix violated: {issue}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
     use super::*;
 
+    #[test]
+    fn error_display() {
+        let err = parse_cookie("no pair").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cookie string is missing a name=value pair"
+        );
+        assert_eq!(
+            ParseError::NoNameValueSeparator.to_string(),
+            "cookie string has no '=' between name and value"
+        );
+
+        let options = ParseOptions {
+            enforce_prefixes: true,
+            ..Default::default()
+        };
+        let err = parse_cookie_with_options("__Secure-id=1", options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cookie name prefix violated: __Secure- and __Host- cookies must be Secure"
+        );
+
+        let boxed: Box<dyn std::error::Error> = Box::new(err);
+        assert!(boxed.source().is_none());
+    }
+
+    #[test]
+    fn no_name_value_separator() {
+        // Still `MissingPair`, so existing matches on it keep catching these
+        assert!(matches!(
+            parse_cookie("novalue; Path=/"),
+            Err(ParseError::MissingPair)
+        ));
+        assert!(matches!(parse_cookie(""), Err(ParseError::MissingPair)));
+    }
+
     #[test]
     fn parse_valid() {
         let date1 = "Tue, 21 Oct 2025 07:28:00 GMT";
@@ -177,13 +997,802 @@ mod tests {
         assert!(parse_date_all_formats(invalid_time).is_err());
     }
 
+    #[test]
+    fn epoch_expires() {
+        let options = ParseOptions {
+            epoch_expires: true,
+            ..ParseOptions::lenient()
+        };
+
+        let cookie = parse_cookie_with_options("a=b; Expires=@1700000000", options).unwrap();
+        assert_eq!(
+            cookie.expires(),
+            Some(Expiration::DateTime(
+                DateTime::<Utc>::from_str("2023-11-14T22:13:20Z").unwrap()
+            ))
+        );
+        assert_eq!(cookie.expires_raw(), Some("@1700000000"));
+
+        assert!(matches!(
+            parse_cookie_with_options("a=b; Expires=@soon", options),
+            Err(ParseError::InvalidDate { .. })
+        ));
+        assert!(matches!(
+            parse_cookie_with_options("a=b; Expires=@1700000000", ParseOptions::lenient()),
+            Err(ParseError::InvalidDate { .. })
+        ));
+
+        for options in [
+            ParseOptions::strict(),
+            ParseOptions::rfc6265_strict(),
+            ParseOptions::default(),
+            ParseOptions {
+                rfc1123_dates_only: true,
+                ..ParseOptions::lenient()
+            },
+        ] {
+            let options = ParseOptions {
+                epoch_expires: true,
+                ..options
+            };
+            assert!(matches!(
+                parse_cookie_with_options("a=b; Expires=@1700000000", options),
+                Err(ParseError::InvalidDate { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_date_keeps_input() {
+        let err = Cookie::parse("a=b; Expires=next tuesday; Path=/").unwrap_err();
+        assert!(matches!(&err, ParseError::InvalidDate { input } if input == "next tuesday"));
+        assert_eq!(
+            err.to_string(),
+            "Expires is not a recognized date: \"next tuesday\""
+        );
+    }
+
+    #[test]
+    fn two_digit_years() {
+        for (date, expected) in [
+            ("Tuesday, 01-Jan-30 00:00:00 GMT", "2030-01-01T00:00:00Z"),
+            ("Tuesday, 01-Jan-80 00:00:00 GMT", "1980-01-01T00:00:00Z"),
+            ("Tuesday, 01-Jan-69 00:00:00 GMT", "2069-01-01T00:00:00Z"),
+            ("Thursday, 01-Jan-70 00:00:00 GMT", "1970-01-01T00:00:00Z"),
+            ("Saturday, 01-Jan-00 00:00:00 GMT", "2000-01-01T00:00:00Z"),
+            ("Friday, 01-Jan-99 00:00:00 GMT", "1999-01-01T00:00:00Z"),
+        ] {
+            let expected = DateTime::<Utc>::from_str(expected).unwrap();
+            assert_eq!(parse_date_all_formats(date).unwrap(), expected, "{date:?}");
+            assert_eq!(
+                parse_date_rfc6265(date, false).unwrap(),
+                expected,
+                "{date:?}"
+            );
+        }
+
+        let input = "a=b; Expires=Tue, 01-Jan-30 00:00:00 GMT";
+        let cookie = Cookie::parse(input).unwrap();
+        assert_eq!(
+            cookie.expires(),
+            Some(Expiration::DateTime(
+                DateTime::<Utc>::from_str("2030-01-01T00:00:00Z").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_rfc6265_dates() {
+        let expected = DateTime::<Utc>::from_str("1994-11-06T08:49:37Z").unwrap();
+
+        for date in [
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+            "Sun Nov 6 08:49:37 1994 GMT",
+            "Sun, 06-Nov-1994 08:49:37 GMT",
+            "Sun, 6 Nov 1994 8:49:37 GMT",
+            "06 Nov 1994 08:49:37",
+            "Wed, 06 november 1994 08:49:37 UTC",
+        ] {
+            assert_eq!(
+                parse_date_rfc6265(date, false).unwrap(),
+                expected,
+                "{date:?}"
+            );
+
+            let input = format!("a=b; Expires={date}");
+            let cookie = Cookie::parse(&input).unwrap();
+            assert_eq!(cookie.expires(), Some(Expiration::DateTime(expected)));
+        }
+
+        for date in [
+            "Sun, 06 Nov 1994",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "Sun, 31 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1600 08:49:37 GMT",
+            "Sun, 06 Foo 1994 08:49:37 GMT",
+        ] {
+            assert!(parse_date_rfc6265(date, false).is_err(), "{date:?}");
+        }
+
+        let input = "a=b; Expires=Sun Nov  6 08:49:37 1994";
+        assert!(parse_cookie_with_options(input, ParseOptions::strict()).is_err());
+    }
+
+    #[test]
+    fn parse_fractional_seconds() {
+        let input = "a=b; Expires=Tue, 21 Oct 2025 07:28:00.5 GMT";
+
+        let cookie = parse_cookie_with_options(input, ParseOptions::lenient()).unwrap();
+        assert!(matches!(
+            cookie.expires(),
+            Some(Expiration::DateTime(date))
+                if date == DateTime::<Utc>::from_str("2025-10-21T07:28:00Z").unwrap()
+        ));
+
+        let input = "a=b; Expires=Tue, 21 Oct 2025 07:28:00.123 GMT";
+        assert!(parse_cookie_with_options(input, ParseOptions::lenient()).is_ok());
+
+        assert!(matches!(
+            Cookie::parse(input),
+            Err(ParseError::InvalidDate { .. })
+        ));
+    }
+
     #[test]
     fn cookie() {
         let cookie = "sessionId=abc123; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=3600; Domain=example.com; Path=/; Secure; HttpOnly; SameSite=Strict";
         let cookie = Cookie::parse(cookie).unwrap();
+        assert_eq!(cookie.name_value(), ("sessionId", "abc123"));
         let cookie = "authToken=xyz789; Expires=Fri, 01 Jan 2027 12:00:00 GMT; Max-Age=7200; Domain=example.org; Path=/account; Secure; HttpOnly; SameSite=Lax";
         let cookie = Cookie::parse(cookie).unwrap();
         println!("{cookie:#?}");
         println!("{cookie}");
     }
+
+    #[test]
+    fn max_age_trailing_junk() {
+        let lenient = ParseOptions::lenient();
+
+        let cookie = parse_cookie_with_options("a=b; Max-Age=3600s", lenient).unwrap();
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(3600)));
+
+        let cookie = parse_cookie_with_options("a=b; Max-Age=3600 foo", lenient).unwrap();
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(3600)));
+
+        let cookie = parse_cookie_with_options("a=b; Max-Age=-10s", lenient).unwrap();
+        assert_eq!(cookie.max_age(), Some(Duration::ZERO));
+
+        let cookie = parse_cookie_with_options("a=b; Max-Age=s3600", lenient).unwrap();
+        assert_eq!(cookie.max_age(), None);
+
+        let cookie = Cookie::parse("a=b; Max-Age=3600s").unwrap();
+        assert_eq!(cookie.max_age(), None);
+
+        let cookie = parse_cookie_with_options("a=b; Max-Age=3_600", lenient).unwrap();
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(3600)));
+
+        let cookie = parse_cookie_with_options("a=b; Max-Age=_3600", lenient).unwrap();
+        assert_eq!(cookie.max_age(), None);
+
+        let cookie = Cookie::parse("a=b; Max-Age=3_600").unwrap();
+        assert_eq!(cookie.max_age(), None);
+        assert!(matches!(
+            parse_cookie_with_options("a=b; Max-Age=3_600", ParseOptions::strict()),
+            Err(ParseError::InvalidMaxAge)
+        ));
+
+        assert!(matches!(
+            parse_cookie_with_options("a=b; Max-Age=3600s", ParseOptions::strict()),
+            Err(ParseError::InvalidMaxAge)
+        ));
+    }
+
+    #[test]
+    fn detect_header_kind() {
+        let header = "a=1; b=2;c=3";
+        match detect_and_parse(header).unwrap() {
+            ParsedHeader::Request(cookies) => {
+                let pairs: Vec<_> = cookies.iter().map(|c| c.name_value()).collect();
+                assert_eq!(pairs, [("a", "1"), ("b", "2"), ("c", "3")]);
+            }
+            ParsedHeader::Response(_) => panic!("Expected a request header"),
+        }
+
+        let header = "id=abc; Path=/; secure";
+        match detect_and_parse(header).unwrap() {
+            ParsedHeader::Response(cookie) => {
+                assert_eq!(cookie.name_value(), ("id", "abc"));
+                assert_eq!(cookie.path(), Some("/"));
+            }
+            ParsedHeader::Request(_) => panic!("Expected a response header"),
+        }
+    }
+
+    #[test]
+    fn quoted_value() {
+        let cookie = Cookie::parse(r#"name="abc def"; Path=/"#).unwrap();
+        assert_eq!(cookie.value(), "abc def");
+        assert_eq!(cookie.value_raw(), Some(r#""abc def""#));
+
+        let cookie = Cookie::parse(r#"name="a\"b""#).unwrap();
+        assert_eq!(cookie.value(), r#"a"b"#);
+        assert_eq!(cookie.value_raw(), Some(r#""a\"b""#));
+
+        let cookie = Cookie::parse(r#"name="a\\b""#).unwrap();
+        assert_eq!(cookie.value(), r#"a\b"#);
+
+        let cookie = Cookie::parse(r#"name=""#).unwrap();
+        assert_eq!(cookie.value(), r#"""#);
+    }
+
+    #[test]
+    fn preserve_value_whitespace() {
+        let input = "name=  spaced  ; Path=/";
+
+        let cookie = Cookie::parse(input).unwrap();
+        assert_eq!(cookie.value(), "spaced");
+        assert_eq!(cookie.value_raw(), Some("spaced"));
+
+        let options = ParseOptions {
+            preserve_value_whitespace: true,
+            ..Default::default()
+        };
+        let cookie = Cookie::parse_with_options(input, options).unwrap();
+        assert_eq!(cookie.value(), "spaced");
+        assert_eq!(cookie.value_raw(), Some("  spaced  "));
+        assert_eq!(cookie.as_forwarded().to_string(), input);
+    }
+
+    #[test]
+    fn quoted_value_with_attributes() {
+        let input = r#"name="hello"; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Secure"#;
+        let cookie = Cookie::parse(input).unwrap();
+
+        assert_eq!(cookie.value(), "hello");
+        assert_eq!(
+            cookie.expires(),
+            Some(Expiration::DateTime(
+                DateTime::parse_from_rfc3339("2025-10-21T07:28:00Z")
+                    .unwrap()
+                    .to_utc()
+            ))
+        );
+        assert_eq!(cookie.secure(), Some(true));
+    }
+
+    #[test]
+    fn quoted_value_trailing_data() {
+        let input = r#"name="abc"junk; Path=/"#;
+
+        assert!(matches!(
+            parse_cookie_with_options(input, ParseOptions::strict()),
+            Err(ParseError::TrailingData)
+        ));
+
+        let cookie = parse_cookie_with_options(input, ParseOptions::lenient()).unwrap();
+        assert_eq!(cookie.value(), "abc");
+        assert_eq!(cookie.value_raw(), Some(r#""abc"junk"#));
+        assert_eq!(cookie.path(), Some("/"));
+
+        let cookie = Cookie::parse(input).unwrap();
+        assert_eq!(cookie.value(), r#""abc"junk"#);
+
+        let cookie = parse_cookie_with_options(r#"name="abc""#, ParseOptions::strict()).unwrap();
+        assert_eq!(cookie.value(), "abc");
+    }
+
+    #[test]
+    fn allowed_attributes() {
+        let input = "a=b; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=3600; Domain=example.com; Path=/; Secure; HttpOnly; SameSite=Strict";
+        let cookie = parse_cookie_with_allowed_attrs(input, &["Path", "secure"]).unwrap();
+
+        assert_eq!(cookie.name_value(), ("a", "b"));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.secure(), Some(true));
+        assert!(cookie.expires().is_none());
+        assert_eq!(cookie.max_age(), None);
+        assert_eq!(cookie.domain(), None);
+        assert_eq!(cookie.http_only(), None);
+        assert!(cookie.same_site().is_none());
+    }
+
+    #[test]
+    fn encoded_value() {
+        let cookie = Cookie::parse_encoded("name=a%20b; Path=/").unwrap();
+        assert_eq!(cookie.value(), "a b");
+        assert_eq!(cookie.value_raw(), Some("a%20b"));
+
+        let cookie = Cookie::parse_encoded(r#"name="a%3B\"b""#).unwrap();
+        assert_eq!(cookie.value(), r#"a;"b"#);
+        assert_eq!(cookie.value_raw(), Some(r#""a%3B\"b""#));
+
+        let cookie = Cookie::parse_encoded("name=plain").unwrap();
+        assert_eq!(cookie.value(), "plain");
+        assert_eq!(cookie.value_raw(), Some("plain"));
+
+        let cookie = Cookie::parse("name=a%20b").unwrap();
+        assert_eq!(cookie.value(), "a%20b");
+
+        let cookie = Cookie::parse_encoded("my%20name=a%20b").unwrap();
+        assert_eq!(cookie.name(), "my name");
+        assert_eq!(cookie.name_raw(), Some("my%20name"));
+        assert_eq!(cookie.value(), "a b");
+        assert_eq!(cookie.as_forwarded().to_string(), "my%20name=a%20b");
+
+        assert!(matches!(
+            Cookie::parse_encoded("%FF=1"),
+            Err(ParseError::Utf8Error)
+        ));
+
+        assert!(matches!(
+            Cookie::parse_encoded("name=%FF"),
+            Err(ParseError::Utf8Error)
+        ));
+    }
+
+    #[test]
+    fn case_insensitive_attributes() {
+        let cookie = Cookie::parse("a=b; secure; httponly").unwrap();
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.http_only(), Some(true));
+
+        let cookie = Cookie::parse("a=b; SECURE; HTTPONLY; path=/; SAMESITE=lax").unwrap();
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.http_only(), Some(true));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+    }
+
+    #[test]
+    fn tab_whitespace() {
+        let cookie = Cookie::parse("name=value;\tPath=/").unwrap();
+        assert_eq!(cookie.path(), Some("/"));
+
+        let cookie = Cookie::parse("\tname\t=\tvalue\t; \t Path = / ;\tSecure\t").unwrap();
+        assert_eq!(cookie.name(), "name");
+        assert_eq!(cookie.value(), "value");
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.secure(), Some(true));
+
+        for input in ["\t", "\t;\t", "a=b;\t\t;\t=\t", "\t=\t;\t\t", "a\t=\t\t\t"] {
+            let _ = Cookie::parse(input);
+            let _ = Cookie::parse_with_options(input, ParseOptions::lenient());
+            let _ = Cookie::parse_with_options(input, ParseOptions::strict());
+        }
+    }
+
+    #[test]
+    fn default_same_site_lax() {
+        let options = ParseOptions {
+            default_same_site_lax: true,
+            ..Default::default()
+        };
+
+        let cookie = parse_cookie_with_options("a=b", options).unwrap();
+        assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+
+        let cookie = parse_cookie_with_options("a=b; SameSite=Strict", options).unwrap();
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+
+        let cookie = Cookie::parse("a=b").unwrap();
+        assert_eq!(cookie.same_site(), None);
+    }
+
+    #[test]
+    fn enforce_prefixes() {
+        let options = ParseOptions {
+            enforce_prefixes: true,
+            ..ParseOptions::strict()
+        };
+
+        assert!(parse_cookie_with_options("__Host-id=1; Path=/; Secure", options).is_ok());
+        assert!(matches!(
+            parse_cookie_with_options("__Host-id=1; Path=/; Domain=evil.com; Secure", options),
+            Err(ParseError::PrefixViolation(
+                ValidationIssue::HostPrefixHasDomain
+            ))
+        ));
+
+        let spoofed = "__Host-id=1; Path=/";
+        assert!(matches!(
+            parse_cookie_with_options(spoofed, options),
+            Err(ParseError::PrefixViolation(
+                ValidationIssue::PrefixRequiresSecure
+            ))
+        ));
+        assert!(parse_cookie_with_options(spoofed, ParseOptions::strict()).is_ok());
+    }
+
+    #[test]
+    fn enforce_same_site_secure() {
+        let options = ParseOptions {
+            enforce_same_site_secure: true,
+            ..ParseOptions::strict()
+        };
+
+        assert!(matches!(
+            parse_cookie_with_options("a=b; SameSite=None", options),
+            Err(ParseError::SameSiteNoneNotSecure)
+        ));
+        assert!(parse_cookie_with_options("a=b; SameSite=None; Secure", options).is_ok());
+        assert!(parse_cookie_with_options("a=b; SameSite=Lax", options).is_ok());
+
+        assert!(parse_cookie_with_options("a=b; SameSite=None", ParseOptions::strict()).is_ok());
+    }
+
+    #[test]
+    fn limits() {
+        let limits = ParseLimits {
+            max_attr_value_len: Some(4096),
+            ..Default::default()
+        };
+
+        let input = format!("a=b; Path=/{}", "p".repeat(1024 * 1024));
+        assert!(matches!(
+            parse_cookie_with_limits(input.as_str(), limits),
+            Err(ParseError::AttributeTooLong)
+        ));
+        assert!(parse_cookie_with_limits("a=b; Path=/short", limits).is_ok());
+        assert!(Cookie::parse(&input).is_ok());
+
+        let limits = ParseLimits {
+            max_len: Some(8),
+            ..Default::default()
+        };
+        assert!(matches!(
+            parse_cookie_with_limits("a=b; Path=/", limits),
+            Err(ParseError::TooLong)
+        ));
+    }
+
+    #[test]
+    fn request_cookies() {
+        let cookies = parse_request_cookies(" a=1;b = 2 ; c=3;").collect::<Vec<_>>();
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies[1].as_ref().unwrap().name_value(), ("b", "2"));
+
+        let cookies = parse_request_cookies("a=1; junk; =2").collect::<Vec<_>>();
+        assert!(cookies[0].is_ok());
+        assert!(matches!(cookies[1], Err(ParseError::MissingPair)));
+        assert!(matches!(cookies[2], Err(ParseError::EmptyName)));
+
+        let cookie = parse_request_cookies("a=1; Path=/")
+            .nth(1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cookie.name_value(), ("Path", "/"));
+        assert_eq!(cookie.path(), None);
+    }
+
+    #[test]
+    fn request_cookies_rfc2965_attributes() {
+        let cookies = parse_request_cookies("$Version=1; session=abc; $Path=/; id=1")
+            .collect::<ParseResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name_value(), ("session", "abc"));
+        assert_eq!(cookies[0].path(), Some("/"));
+        assert_eq!(cookies[1].path(), None);
+
+        let cookie = parse_request_cookies(r#"a=1; $Path="/app"; $Domain=.example.com"#)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(cookie.path(), Some("/app"));
+        assert_eq!(cookie.domain(), Some("example.com"));
+    }
+
+    #[test]
+    fn max_cookies() {
+        let limits = ParseLimits {
+            max_cookies: Some(3),
+            ..Default::default()
+        };
+
+        let header = (0..1000)
+            .map(|i| format!("c{i}={i}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+        assert!(matches!(
+            parse_request_with_limits(&header, limits),
+            Err(ParseError::TooManyCookies)
+        ));
+
+        // Malformed pairs count against the limit too
+        assert!(matches!(
+            parse_request_with_limits("a=1; junk; =2; c=3", limits),
+            Err(ParseError::TooManyCookies)
+        ));
+
+        let cookies = parse_request_with_limits("a=1; junk; c=3", limits).unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[1].name_value(), ("c", "3"));
+
+        assert!(matches!(
+            detect_and_parse(&header),
+            Err(ParseError::TooManyCookies)
+        ));
+    }
+
+    #[test]
+    fn ip_literal_domain() {
+        for input in ["a=b; Domain=127.0.0.1", "a=b; Domain=[::1]"] {
+            let cookie = Cookie::parse(input).unwrap();
+            assert_eq!(cookie.domain(), None);
+
+            let cookie = parse_cookie_with_options(input, ParseOptions::lenient()).unwrap();
+            assert_eq!(cookie.domain(), None);
+
+            assert!(matches!(
+                parse_cookie_with_options(input, ParseOptions::strict()),
+                Err(ParseError::InvalidDomain)
+            ));
+        }
+
+        let cookie = parse_cookie_with_options("a=b; Domain=example.com", ParseOptions::strict());
+        assert_eq!(cookie.unwrap().domain(), Some("example.com"));
+    }
+
+    #[test]
+    fn parse_strict() {
+        assert!(Cookie::parse_strict("id=1; Path=/").is_ok());
+        assert!(matches!(
+            Cookie::parse_strict("user id=1"),
+            Err(ParseError::InvalidName)
+        ));
+        assert!(matches!(
+            Cookie::parse_strict("a=b; Max-Age=soon"),
+            Err(ParseError::InvalidMaxAge)
+        ));
+
+        assert_eq!(Cookie::parse("user id=1").unwrap().name(), "user id");
+
+        let cookie = Cookie::parse_strict(r#"id="abc"; Path=/"#).unwrap();
+        assert_eq!(cookie.value(), "abc");
+        assert!(matches!(
+            Cookie::parse_strict(r#"id="a b, c"; Path=/"#),
+            Err(ParseError::InvalidValue)
+        ));
+        assert!(matches!(
+            Cookie::parse_strict("id=a,b"),
+            Err(ParseError::InvalidValue)
+        ));
+    }
+
+    #[test]
+    fn rfc6265_strict() {
+        let options = ParseOptions::rfc6265_strict();
+
+        let conformant = [
+            "id=1",
+            "id=\"quoted\"",
+            "id=1; Expires=Tue, 21 Oct 2025 07:28:00 GMT",
+            "id=1; Max-Age=3600",
+            "id=1; Max-Age=-1",
+            "id=1; Domain=example.com; Path=/; Secure; HttpOnly",
+            "id=1; SameSite=Strict",
+            "id=1;\tPath=/",
+        ];
+        for input in conformant {
+            assert!(
+                parse_cookie_with_options(input, options).is_ok(),
+                "{input:?}"
+            );
+        }
+
+        let nonconformant = [
+            "id=1; Expires=Tuesday, 21-Oct-25 07:28:00 GMT",
+            "id=1; Expires=Tue, Oct 21 07:28:00 2025",
+            "id=1; Max-Age=1h",
+            "id=1; Max-Age=",
+            "id=1; SameSite=Relaxed",
+            "id=1; Priority=urgent",
+            "id=1; Path=/\u{1}",
+            "i d=1",
+            "id=a,b",
+            "id=\"a\"junk",
+        ];
+        for input in nonconformant {
+            assert!(
+                parse_cookie_with_options(input, options).is_err(),
+                "{input:?}"
+            );
+        }
+
+        assert!(matches!(
+            Cookie::parse_strict("id=1; Path=/\u{7f}"),
+            Err(ParseError::ControlCharacter)
+        ));
+        assert!(matches!(
+            Cookie::parse_strict("id=1; SameSite=Relaxed"),
+            Err(ParseError::InvalidSameSite)
+        ));
+        assert!(Cookie::parse("id=1; SameSite=Relaxed").is_ok());
+    }
+
+    #[test]
+    fn partitioned() {
+        let input = "a=b; Path=/; Secure; SameSite=None; partitioned";
+        let cookie = Cookie::parse(input).unwrap();
+        assert_eq!(cookie.partitioned(), Some(true));
+        assert_eq!(
+            cookie.to_string(),
+            "a=b; Path=/; Secure; SameSite=None; Partitioned"
+        );
+
+        assert_eq!(Cookie::parse("a=b").unwrap().partitioned(), None);
+    }
+
+    #[test]
+    fn priority() {
+        let cookie = Cookie::parse("a=b; Priority=high; Secure").unwrap();
+        assert_eq!(cookie.priority(), Some(Priority::High));
+        assert_eq!(cookie.to_string(), "a=b; Secure; Priority=High");
+        assert_eq!(cookie.extensions().count(), 0);
+
+        let cookie = Cookie::parse("a=b; Priority=urgent").unwrap();
+        assert_eq!(cookie.priority(), None);
+        assert_eq!(cookie.to_string(), "a=b");
+        assert!(matches!(
+            parse_cookie_with_options("a=b; Priority=urgent", ParseOptions::strict()),
+            Err(ParseError::InvalidPriority)
+        ));
+
+        let cookie = Cookie::builder("a", "b").priority(Priority::Low).build();
+        assert_eq!(cookie.to_string(), "a=b; Priority=Low");
+    }
+
+    #[test]
+    fn quoted_same_site() {
+        let cookie = Cookie::parse(r#"a=b; SameSite=" Lax ""#).unwrap();
+        assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+        assert_eq!(cookie.same_site_raw(), Some(r#"" Lax ""#));
+
+        let cookie = Cookie::parse(r#"a=b; SameSite="Strict""#).unwrap();
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+    }
+
+    #[test]
+    fn empty_attribute_name() {
+        let (cookie, warnings) = parse_cookie_with_warnings("a=b;   =foo; ; Path=/").unwrap();
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.extensions().count(), 0);
+        assert_eq!(cookie.to_string(), "a=b; Path=/");
+        assert_eq!(warnings, [ParseWarning::EmptyAttributeName]);
+
+        let (_, warnings) = parse_cookie_with_warnings("a=b; ;;  ; Path=/").unwrap();
+        assert!(warnings.is_empty());
+
+        assert!(parse_cookie_with_options("a=b; =foo", ParseOptions::strict()).is_ok());
+    }
+
+    #[test]
+    fn set_cookie_block() {
+        let block = "Set-Cookie: a=1; Path=/\r\nset-cookie:b=2; Secure\n\nSet-Cookie: =3\n";
+        let cookies = parse_set_cookie_block(block);
+
+        assert_eq!(cookies.len(), 3);
+        let a = cookies[0].as_ref().unwrap();
+        assert_eq!(a.name_value(), ("a", "1"));
+        assert_eq!(a.path(), Some("/"));
+        let b = cookies[1].as_ref().unwrap();
+        assert_eq!(b.name_value(), ("b", "2"));
+        assert_eq!(b.secure(), Some(true));
+        assert!(matches!(cookies[2], Err(ParseError::EmptyName)));
+    }
+
+    #[test]
+    fn empty_same_site() {
+        let (cookie, warnings) = parse_cookie_with_warnings("a=b; SameSite=; Path=/").unwrap();
+        assert_eq!(cookie.same_site(), None);
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(warnings, [ParseWarning::EmptySameSite]);
+
+        let cookie = parse_cookie_with_options("a=b; SameSite=", ParseOptions::strict()).unwrap();
+        assert_eq!(cookie.same_site(), None);
+    }
+
+    #[test]
+    fn already_expired_warning() {
+        let (_, warnings) =
+            parse_cookie_with_warnings("a=b; Expires=Thu, 01 Jan 1970 00:00:00 GMT").unwrap();
+        assert_eq!(warnings, [ParseWarning::AlreadyExpired]);
+
+        let (_, warnings) = parse_cookie_with_warnings("a=b; Max-Age=0").unwrap();
+        assert_eq!(warnings, [ParseWarning::AlreadyExpired]);
+
+        let (_, warnings) =
+            parse_cookie_with_warnings("a=b; Expires=Thu, 01 Jan 1970 00:00:00 GMT; Max-Age=3600")
+                .unwrap();
+        assert!(warnings.is_empty());
+
+        let (_, warnings) = parse_cookie_with_warnings("a=b").unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn parse_lines() {
+        let block = "a=1; Path=/\r\nb=2; Secure\r\n";
+
+        let (cookie, rest) = parse_cookie_line(block).unwrap();
+        assert_eq!(cookie.name_value(), ("a", "1"));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(rest, "b=2; Secure\r\n");
+
+        let (cookie, rest) = parse_cookie_line(rest).unwrap();
+        assert_eq!(cookie.name_value(), ("b", "2"));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(rest, "");
+
+        let (cookie, rest) = parse_cookie_line("c=3").unwrap();
+        assert_eq!(cookie.name_value(), ("c", "3"));
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn attribute_value_with_equals() {
+        let cookie = Cookie::parse("a=b; Path=/a=b; Domain=x=y").unwrap();
+        assert_eq!(cookie.path(), Some("/a=b"));
+        assert_eq!(cookie.domain(), Some("x=y"));
+
+        let cookie = Cookie::parse("a=b=c; Path=/search?q=a=b").unwrap();
+        assert_eq!(cookie.name_value(), ("a", "b=c"));
+        assert_eq!(cookie.path(), Some("/search?q=a=b"));
+    }
+
+    #[test]
+    fn parse_os() {
+        let cookie = parse_cookie_os(OsStr::new("a=b; Path=/")).unwrap();
+        assert_eq!(cookie.name_value(), ("a", "b"));
+        assert_eq!(cookie.path(), Some("/"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_os_invalid_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let s = OsStr::from_bytes(b"a=\xFF");
+        assert!(matches!(parse_cookie_os(s), Err(ParseError::Utf8Error)));
+    }
+
+    #[test]
+    fn parse_into_scratch() {
+        let mut scratch = String::new();
+
+        let cookie = parse_cookie_into(r#"a="x\"y"; Path=/"#, &mut scratch).unwrap();
+        assert_eq!(cookie.value(), r#"x"y"#);
+        assert_eq!(cookie.path(), Some("/"));
+        drop(cookie);
+
+        let cookie = parse_cookie_into(r#"b="1\\2""#, &mut scratch).unwrap();
+        assert_eq!(cookie.name_value(), ("b", r#"1\2"#));
+        assert_eq!(cookie.value_raw(), Some(r#""1\\2""#));
+        drop(cookie);
+
+        assert_eq!(scratch, r#"1\2"#);
+    }
+
+    #[test]
+    fn raw_attributes() {
+        let cookie =
+            Cookie::parse("a=b; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=0042").unwrap();
+        assert_eq!(cookie.expires_raw(), Some("Tue, 21 Oct 2025 07:28:00 GMT"));
+        assert_eq!(cookie.max_age_raw(), Some("0042"));
+        assert_eq!(cookie.max_age(), Some(Duration::from_secs(42)));
+
+        let cookie =
+            parse_cookie_with_options("a=b; Max-Age=3600s", ParseOptions::lenient()).unwrap();
+        assert_eq!(cookie.max_age_raw(), Some("3600s"));
+
+        let mut cookie = Cookie::parse("a=b; Max-Age=10").unwrap();
+        cookie.set_max_age(Duration::from_secs(20));
+        assert_eq!(cookie.max_age_raw(), None);
+
+        let cookie = Cookie::parse("a=b").unwrap();
+        assert_eq!(cookie.expires_raw(), None);
+        assert_eq!(cookie.max_age_raw(), None);
+    }
 }
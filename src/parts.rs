@@ -0,0 +1,71 @@
+use std::{borrow::Cow, time::Duration};
+
+use crate::{
+    Cookie, cookie::CookieStr, expires::Expiration, priority::Priority, same_site::SameSite,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieParts {
+    pub name: String,
+    pub value: String,
+    pub expires: Option<Expiration>,
+    pub max_age: Option<Duration>,
+    pub domain: Option<String>,
+    pub path: Option<String>,
+    pub secure: Option<bool>,
+    pub http_only: Option<bool>,
+    pub same_site: Option<SameSite>,
+    pub partitioned: Option<bool>,
+    pub priority: Option<Priority>,
+    /// Unknown attributes as key and optional value, in order.
+    pub extensions: Vec<(String, Option<String>)>,
+}
+
+impl From<CookieParts> for Cookie<'static> {
+    fn from(parts: CookieParts) -> Self {
+        let mut cookie = Cookie::from_name_value(
+            CookieStr::Concrete(Cow::Owned(parts.name)),
+            CookieStr::Concrete(Cow::Owned(parts.value)),
+        );
+
+        cookie.expires = parts.expires;
+        cookie.max_age = parts.max_age;
+        cookie.domain = parts.domain.map(|d| CookieStr::Concrete(Cow::Owned(d)));
+        cookie.path = parts.path.map(|p| CookieStr::Concrete(Cow::Owned(p)));
+        cookie.secure = parts.secure;
+        cookie.http_only = parts.http_only;
+        cookie.same_site = parts.same_site;
+        cookie.partitioned = parts.partitioned;
+        cookie.priority = parts.priority;
+        cookie.extensions = parts
+            .extensions
+            .into_iter()
+            .map(|(key, val)| {
+                (
+                    CookieStr::Concrete(Cow::Owned(key)),
+                    val.map(|v| CookieStr::Concrete(Cow::Owned(v))),
+                )
+            })
+            .collect();
+
+        cookie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let input = "id=abc; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=60; Domain=.example.com; Path=/; Secure; HttpOnly; SameSite=Strict; Partitioned; Priority=High; SameParty";
+        let parts = Cookie::parse(input).unwrap().into_parts();
+
+        let cookie = Cookie::from(parts.clone());
+        assert_eq!(
+            cookie.to_string(),
+            Cookie::parse(input).unwrap().to_string()
+        );
+        assert_eq!(cookie.into_parts(), parts);
+    }
+}
@@ -0,0 +1,45 @@
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+
+/// The Chromium `Priority` attribute, deciding which cookies are evicted first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl FromStr for Priority {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("low") {
+            Ok(Priority::Low)
+        } else if s.eq_ignore_ascii_case("medium") {
+            Ok(Priority::Medium)
+        } else if s.eq_ignore_ascii_case("high") {
+            Ok(Priority::High)
+        } else {
+            Err(ParseError::InvalidPriority)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert!(matches!("Low".parse::<Priority>(), Ok(Priority::Low)));
+        assert!(matches!("medium".parse::<Priority>(), Ok(Priority::Medium)));
+        assert!(matches!("HIGH".parse::<Priority>(), Ok(Priority::High)));
+        assert!(matches!(
+            "Urgent".parse::<Priority>(),
+            Err(ParseError::InvalidPriority)
+        ));
+    }
+}
@@ -1,7 +1,44 @@
-#[derive(Debug, Clone, Copy, Default)]
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SameSite {
     Strict,
     Lax,
     #[default]
     None,
 }
+
+impl FromStr for SameSite {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("strict") {
+            Ok(SameSite::Strict)
+        } else if s.eq_ignore_ascii_case("lax") {
+            Ok(SameSite::Lax)
+        } else if s.eq_ignore_ascii_case("none") {
+            Ok(SameSite::None)
+        } else {
+            Err(ParseError::InvalidSameSite)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        assert!(matches!("Strict".parse::<SameSite>(), Ok(SameSite::Strict)));
+        assert!(matches!("lax".parse::<SameSite>(), Ok(SameSite::Lax)));
+        assert!(matches!("NONE".parse::<SameSite>(), Ok(SameSite::None)));
+        assert!(matches!(
+            "Relaxed".parse::<SameSite>(),
+            Err(ParseError::InvalidSameSite)
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Cookie, CookieParts, Expiration, Priority, SameSite};
+
+// `S` is `&str` when serializing and `String` when deserializing
+#[derive(Serialize, Deserialize)]
+struct CookieRepr<S> {
+    name: S,
+    value: S,
+    expires: Option<Expiration>,
+    /// Whole seconds.
+    max_age: Option<u64>,
+    domain: Option<S>,
+    path: Option<S>,
+    secure: Option<bool>,
+    http_only: Option<bool>,
+    same_site: Option<SameSite>,
+    partitioned: Option<bool>,
+    priority: Option<Priority>,
+    #[serde(default)]
+    extensions: Vec<(S, Option<S>)>,
+}
+
+impl<'a> Serialize for Cookie<'a> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        CookieRepr {
+            name: self.name(),
+            value: self.value(),
+            expires: self.expires(),
+            max_age: self.max_age().map(|max_age| max_age.as_secs()),
+            domain: self.domain_with_dot(),
+            path: self.path(),
+            secure: self.secure(),
+            http_only: self.http_only(),
+            same_site: self.same_site(),
+            partitioned: self.partitioned(),
+            priority: self.priority(),
+            extensions: self.extensions().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cookie<'static> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = CookieRepr::<String>::deserialize(deserializer)?;
+
+        Ok(Cookie::from(CookieParts {
+            name: repr.name,
+            value: repr.value,
+            expires: repr.expires,
+            max_age: repr.max_age.map(Duration::from_secs),
+            domain: repr.domain,
+            path: repr.path,
+            secure: repr.secure,
+            http_only: repr.http_only,
+            same_site: repr.same_site,
+            partitioned: repr.partitioned,
+            priority: repr.priority,
+            extensions: repr.extensions,
+        }))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl Cookie<'_> {
+    /// Returns the cookie as a JSON object for structured logs. Unlike the
+    /// `Serialize` impl, `expires` is in Unix seconds, and `null` for session
+    /// cookies.
+    pub fn to_json_value(&self) -> serde_json::Value {
+        let expires = self.expires().and_then(|expires| match expires {
+            Expiration::DateTime(date) => Some(date.timestamp()),
+            Expiration::Session => None,
+        });
+
+        serde_json::json!({
+            "name": self.name(),
+            "value": self.value(),
+            "expires": expires,
+            "max_age": self.max_age().map(|max_age| max_age.as_secs()),
+            "domain": self.domain_with_dot(),
+            "path": self.path(),
+            "secure": self.secure(),
+            "http_only": self.http_only(),
+            "same_site": self.same_site(),
+            "partitioned": self.partitioned(),
+            "priority": self.priority(),
+            "extensions": self.extensions().collect::<Vec<_>>(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let input = "id=abc; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=60; \
+            Domain=.example.com; Path=/; Secure; HttpOnly; SameSite=Lax; SameParty";
+        let cookie = Cookie::parse(input).unwrap();
+
+        let json = serde_json::to_value(&cookie).unwrap();
+        assert_eq!(json["name"], "id");
+        assert_eq!(json["expires"]["DateTime"], "2025-10-21T07:28:00Z");
+        assert_eq!(json["max_age"], 60);
+        assert_eq!(json["domain"], ".example.com");
+        assert_eq!(json["same_site"], "Lax");
+
+        let back: Cookie<'static> = serde_json::from_value(json).unwrap();
+        assert_eq!(back, cookie);
+        assert_eq!(back.to_string(), cookie.to_string());
+        assert_eq!(back.extensions().collect::<Vec<_>>(), [("SameParty", None)]);
+    }
+
+    #[test]
+    fn session_expiration() {
+        let cookie = Cookie::builder("id", "1")
+            .expires(Expiration::Session)
+            .build();
+        let json = serde_json::to_string(&cookie).unwrap();
+        let back: Cookie<'static> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back.expires(), Some(Expiration::Session));
+        assert_eq!(back.name(), "id");
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn to_json_value() {
+        let input = "id=abc; Expires=Tue, 21 Oct 2025 07:28:00 GMT; Max-Age=60; \
+            Domain=.example.com; Path=/; Secure; HttpOnly; SameSite=Lax; Partitioned; \
+            Priority=High; SameParty; Foo=bar";
+        let cookie = Cookie::parse(input).unwrap();
+
+        assert_eq!(
+            cookie.to_json_value(),
+            serde_json::json!({
+                "name": "id",
+                "value": "abc",
+                "expires": 1761031680,
+                "max_age": 60,
+                "domain": ".example.com",
+                "path": "/",
+                "secure": true,
+                "http_only": true,
+                "same_site": "Lax",
+                "partitioned": true,
+                "priority": "High",
+                "extensions": [["SameParty", null], ["Foo", "bar"]],
+            })
+        );
+    }
+}
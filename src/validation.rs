@@ -0,0 +1,313 @@
+use std::fmt::Display;
+
+use crate::{Cookie, ParseError, same_site::SameSite};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationIssue {
+    EmptyName,
+    InvalidNameChar,
+    InvalidValueChar,
+    SameSiteNoneNotSecure,
+    Oversized,
+    /// A `Partitioned` cookie without `Secure`, which browsers reject.
+    PartitionedNotSecure,
+    /// A `Domain` that is an IP address, which browsers ignore.
+    IpLiteralDomain,
+    /// A `__Secure-` or `__Host-` cookie without `Secure`.
+    PrefixRequiresSecure,
+    /// A `__Host-` cookie with a `Domain`.
+    HostPrefixHasDomain,
+    /// A `__Host-` cookie without `Path=/`.
+    HostPrefixPathNotRoot,
+    /// A `__Host-` cookie with `SameSite=None`. Allowed, but usually a mistake.
+    HostPrefixSameSiteNone,
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ValidationIssue::EmptyName => "cookie name is empty",
+            ValidationIssue::InvalidNameChar => "cookie name contains a non-token character",
+            ValidationIssue::InvalidValueChar => "cookie value contains an invalid character",
+            ValidationIssue::SameSiteNoneNotSecure => {
+                "SameSite=None cookie is missing the Secure attribute"
+            }
+            ValidationIssue::PartitionedNotSecure => {
+                "Partitioned cookie is missing the Secure attribute"
+            }
+            ValidationIssue::IpLiteralDomain => "Domain must not be an IP address",
+            ValidationIssue::Oversized => "cookie exceeds the 4096 byte browser limit",
+            ValidationIssue::PrefixRequiresSecure => "__Secure- and __Host- cookies must be Secure",
+            ValidationIssue::HostPrefixHasDomain => "__Host- cookies must not set a Domain",
+            ValidationIssue::HostPrefixPathNotRoot => "__Host- cookies must have Path=/",
+            ValidationIssue::HostPrefixSameSiteNone => "__Host- cookie uses SameSite=None",
+        })
+    }
+}
+
+/// Returns whether `name` is a valid cookie name, i.e. a non-empty RFC 2616
+/// token without control characters, whitespace or separators.
+pub fn is_valid_cookie_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(is_token_char)
+}
+
+impl<'a> Cookie<'a> {
+    /// Checks the constraints the `__Secure-` and `__Host-` name prefixes
+    /// put on a cookie. Cookies without a prefix always pass.
+    pub fn validate_prefix(&self) -> Result<(), ValidationIssue> {
+        let name = self.name();
+        let is_host = name.starts_with("__Host-");
+
+        if (is_host || name.starts_with("__Secure-")) && !self.secure_required() {
+            return Err(ValidationIssue::PrefixRequiresSecure);
+        }
+        if is_host && self.domain.is_some() {
+            return Err(ValidationIssue::HostPrefixHasDomain);
+        }
+        if is_host && self.path() != Some("/") {
+            return Err(ValidationIssue::HostPrefixPathNotRoot);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the cookie against the RFC 6265 grammar that parsing doesn't
+    /// enforce on its own. The name has to be a token, i.e. no control
+    /// characters, whitespace or separators like `()<>@,;:\"/[]?={}`.
+    ///
+    /// The value is checked as it was written, against the same grammar as
+    /// `is_valid_cookie_value`: cookie-octets, optionally wrapped in double
+    /// quotes.
+    pub fn validate(&self) -> Result<(), ParseError> {
+        let name = self.name();
+        if name.is_empty() {
+            return Err(ParseError::EmptyName);
+        }
+        if !is_valid_cookie_name(name) {
+            return Err(ParseError::InvalidName);
+        }
+
+        if !is_valid_cookie_value(self.wire_value()) {
+            return Err(ParseError::InvalidValue);
+        }
+
+        Ok(())
+    }
+
+    // The value as it was written, including any quotes parsing removed
+    fn wire_value(&self) -> &str {
+        self.value_raw().unwrap_or(self.value()).trim()
+    }
+
+    /// Returns the index and byte of the first value character outside the
+    /// RFC 6265 cookie-octet grammar, or `None` if the value is valid.
+    pub fn first_invalid_value_byte(&self) -> Option<(usize, u8)> {
+        first_invalid_value_byte(self.value())
+    }
+
+    /// Reports prefix usage that is valid but unusual. Never fails.
+    pub fn prefix_advisory(&self) -> Option<ValidationIssue> {
+        if self.name().starts_with("__Host-") && self.same_site() == Some(SameSite::None) {
+            return Some(ValidationIssue::HostPrefixSameSiteNone);
+        }
+
+        None
+    }
+}
+
+// RFC 6265 cookie-name is an RFC 2616 token
+pub(crate) fn is_token_char(c: u8) -> bool {
+    c.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&c)
+}
+
+// RFC 6265 cookie-octet
+pub(crate) fn is_cookie_octet(c: u8) -> bool {
+    matches!(c, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+/// Returns whether `value` only contains RFC 6265 cookie-octets, optionally
+/// wrapped in a pair of double quotes. Whitespace, `"`, `,`, `;` and `\`
+/// need to be encoded first.
+pub fn is_valid_cookie_value(value: &str) -> bool {
+    first_invalid_value_byte(value).is_none()
+}
+
+// Indices are into `value` itself, so the opening quote of a quoted value
+// shifts the interior by one
+pub(crate) fn first_invalid_value_byte(value: &str) -> Option<(usize, u8)> {
+    let (offset, inner) = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => (1, inner),
+        None => (0, value),
+    };
+
+    inner
+        .bytes()
+        .position(|c| !is_cookie_octet(c))
+        .map(|i| (i + offset, inner.as_bytes()[i]))
+}
+
+pub(crate) fn validation_issues(cookie: &Cookie) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    if cookie.name().is_empty() {
+        issues.push(ValidationIssue::EmptyName);
+    } else if !is_valid_cookie_name(cookie.name()) {
+        issues.push(ValidationIssue::InvalidNameChar);
+    }
+    if !is_valid_cookie_value(cookie.wire_value()) {
+        issues.push(ValidationIssue::InvalidValueChar);
+    }
+    if cookie.same_site() == Some(SameSite::None) && !cookie.secure_required() {
+        issues.push(ValidationIssue::SameSiteNoneNotSecure);
+    }
+    if cookie.partitioned() == Some(true) && !cookie.secure_required() {
+        issues.push(ValidationIssue::PartitionedNotSecure);
+    }
+    if cookie.exceeds_browser_limit() {
+        issues.push(ValidationIssue::Oversized);
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_invalid_value_byte() {
+        let cookie = Cookie::builder("id", "ab;cd").build();
+        assert_eq!(cookie.first_invalid_value_byte(), Some((2, b';')));
+
+        let cookie = Cookie::builder("id", "\"a b\"").build();
+        assert_eq!(cookie.first_invalid_value_byte(), Some((2, b' ')));
+
+        let cookie = Cookie::builder("id", "abc").build();
+        assert_eq!(cookie.first_invalid_value_byte(), None);
+    }
+
+    #[test]
+    fn valid_cookie_name() {
+        for name in ["id", "session_id", "__Host-id", "a.b", "A1!#$%&'*+-^`|~"] {
+            assert!(is_valid_cookie_name(name), "{name:?}");
+        }
+        for name in [
+            "", " ", "a b", " id", "id ", "a=b", "a;b", "a,b", "a:b", "a\tb", "é",
+        ] {
+            assert!(!is_valid_cookie_name(name), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn valid_cookie_value() {
+        for value in ["", "abc", "a-b_c.d", "e30=", "\"abc\"", "\"\""] {
+            assert!(is_valid_cookie_value(value), "{value:?}");
+        }
+        for value in ["a b", "a;b", "a,b", "a\\b", "a\"b", "\"a b\"", "\"abc", "é"] {
+            assert!(!is_valid_cookie_value(value), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn validate() {
+        assert!(Cookie::parse("session_id=1").unwrap().validate().is_ok());
+
+        for name in [
+            "a b", "a(b)", "a@b", "a/b", "a\"b", "a[b]", "a{b}", "a?b", "a\tb", "a\u{7f}b",
+        ] {
+            let cookie = Cookie::builder(name, "1").build();
+            assert!(
+                matches!(cookie.validate(), Err(ParseError::InvalidName)),
+                "{name:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_value() {
+        let cookie = Cookie::parse(r#"a="hello""#).unwrap();
+        assert_eq!(cookie.value(), "hello");
+        assert!(cookie.validate().is_ok());
+
+        let cookie = Cookie::parse(r#"a="hello world""#).unwrap();
+        assert_eq!(cookie.value(), "hello world");
+        assert!(matches!(cookie.validate(), Err(ParseError::InvalidValue)));
+
+        for value in ["b,c", "b c", "b;c", "b\\c", "b\"c"] {
+            let cookie = Cookie::builder("a", value).build();
+            assert!(
+                matches!(cookie.validate(), Err(ParseError::InvalidValue)),
+                "{value:?}"
+            );
+        }
+
+        let cookie = Cookie::builder("a", "\"b\u{1}\"").build();
+        assert!(matches!(cookie.validate(), Err(ParseError::InvalidValue)));
+    }
+
+    #[test]
+    fn value_checks_agree() {
+        for (value, valid) in [("\"hello world\"", false), ("\"hello\"", true)] {
+            let built = Cookie::builder("a", value).build();
+            assert_eq!(built.validate().is_ok(), valid, "{value:?}");
+
+            let (_, issues) = Cookie::builder("a", value).build_validated();
+            assert_eq!(
+                !issues.contains(&ValidationIssue::InvalidValueChar),
+                valid,
+                "{value:?}"
+            );
+
+            let mut cookie = Cookie::builder("a", "b").build();
+            assert_eq!(cookie.try_set_value(value).is_ok(), valid, "{value:?}");
+
+            let input = format!("a={value}");
+            let parsed = Cookie::parse(&input).unwrap();
+            assert_eq!(parsed.validate().is_ok(), valid, "{value:?}");
+            assert_eq!(Cookie::parse_strict(&input).is_ok(), valid, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn validate_prefix() {
+        let cookie = Cookie::parse("__Host-id=1; Path=/; Secure").unwrap();
+        assert_eq!(cookie.validate_prefix(), Ok(()));
+
+        let cookie = Cookie::parse("__Host-id=1; Path=/").unwrap();
+        assert_eq!(
+            cookie.validate_prefix(),
+            Err(ValidationIssue::PrefixRequiresSecure)
+        );
+
+        let cookie = Cookie::parse("__Host-id=1; Path=/; Domain=example.com; Secure").unwrap();
+        assert_eq!(
+            cookie.validate_prefix(),
+            Err(ValidationIssue::HostPrefixHasDomain)
+        );
+
+        let cookie = Cookie::parse("__Host-id=1; Path=/app; Secure").unwrap();
+        assert_eq!(
+            cookie.validate_prefix(),
+            Err(ValidationIssue::HostPrefixPathNotRoot)
+        );
+
+        let cookie = Cookie::parse("__Secure-id=1; Domain=example.com; Secure").unwrap();
+        assert_eq!(cookie.validate_prefix(), Ok(()));
+
+        let cookie = Cookie::parse("id=1").unwrap();
+        assert_eq!(cookie.validate_prefix(), Ok(()));
+    }
+
+    #[test]
+    fn host_prefix_same_site_none_advisory() {
+        let cookie = Cookie::parse("__Host-id=1; Path=/; Secure; SameSite=None").unwrap();
+        assert_eq!(cookie.validate_prefix(), Ok(()));
+        assert_eq!(
+            cookie.prefix_advisory(),
+            Some(ValidationIssue::HostPrefixSameSiteNone)
+        );
+
+        let cookie = Cookie::parse("__Host-id=1; Path=/; Secure; SameSite=Lax").unwrap();
+        assert_eq!(cookie.prefix_advisory(), None);
+    }
+}